@@ -0,0 +1,62 @@
+//! Build check for `booking_kit` without `std`.
+//!
+//! This crate is `#![no_std]` and defines its own panic handler, so it fails to build if `std`
+//! ends up in the dependency graph through `booking_kit` or one of its dependencies. It is not
+//! part of the main package; CI builds it for a target without `std` through the cargo alias
+//! defined in `.cargo/config.toml`:
+//!
+//! ```text
+//! rustup target add thumbv7em-none-eabihf
+//! cargo no-std-check
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use booking_kit::error::BookingError;
+use booking_kit::manager::BookingManager;
+use booking_kit::model::booking::Booking;
+use booking_kit::model::status::BookingStatus;
+use booking_kit::policy::TransitionPolicy;
+use booking_kit::traits::Bookable;
+use core::panic::PanicInfo;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Kiosk {
+    id: &'static str,
+}
+
+impl Bookable for Kiosk {
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn is_available(&self) -> bool {
+        true
+    }
+}
+
+/// Creates, confirms and completes a booking with the stateless manager API.
+pub fn lifecycle(id: u32, start: i64) -> Result<Booking<Kiosk, u32, i64, ()>, BookingError> {
+    let mut booking = BookingManager::create(id, None, Kiosk { id: "K1" }, start, Some(start + 3_600), None);
+    BookingManager::confirm(&mut booking)?;
+    BookingManager::complete(&mut booking)?;
+    Ok(booking)
+}
+
+/// Returns the statuses the default transition policy allows from `from`.
+pub fn allowed_from(from: BookingStatus) -> Vec<BookingStatus> {
+    TransitionPolicy::<BookingStatus>::default()
+        .transitions()
+        .iter()
+        .filter(|transition| transition.from == from)
+        .map(|transition| transition.to.clone())
+        .collect()
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {}
+}
@@ -2,6 +2,7 @@
 use serde::{Deserialize, Serialize};
 use booking_kit::error::BookingError;
 use booking_kit::manager::BookingManager;
+use booking_kit::repository::memory::InMemoryBookingRepository;
 use booking_kit::traits::Bookable;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -54,7 +55,7 @@ impl Bookable for HotelRoom {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct FlightSegment {
     id: String,
     seat_quota: u32,
@@ -69,6 +70,10 @@ impl Bookable for FlightSegment {
     fn is_available(&self) -> bool {
         self.seat_quota > 0 && self.status == "Scheduled"
     }
+
+    fn capacity(&self) -> Option<u32> {
+        Some(self.seat_quota)
+    }
 }
 
 #[derive(Debug)]
@@ -76,6 +81,7 @@ struct EventTicket {
     id: String,
     event_start: i64, // unix timestamp
     current_time: i64,
+    total_quota: u32,
     remaining_quota: u32,
 }
 
@@ -87,6 +93,10 @@ impl Bookable for EventTicket {
     fn is_available(&self) -> bool {
         self.remaining_quota > 0 && self.current_time < self.event_start
     }
+
+    fn capacity(&self) -> Option<u32> {
+        Some(self.total_quota)
+    }
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -174,6 +184,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         id: "event-123".into(),
         event_start: 1_600_000_000,     // di masa lalu
         current_time: 1_700_000_000,    // sekarang (lebih besar)
+        total_quota: 500,
         remaining_quota: 0,
     };
 
@@ -192,5 +203,25 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         Err(e) => println!("Error: {}", e),
     }
 
+    println!("\n=== FlightSegment (quantity reservations against seat quota) ===");
+    let manager = BookingManager::new(InMemoryBookingRepository::new());
+    for (booking_id, seats) in [("booking-006", 2), ("booking-007", 1), ("booking-008", 1)] {
+        let segment = FlightSegment {
+            id: "segment-002".into(),
+            seat_quota: 3,
+            status: "Scheduled".into(),
+        };
+        let booking = BookingManager::create(booking_id, Some("user-006"), segment, "now", None, None::<()>)
+            .with_quantity(seats);
+
+        match manager.try_insert(booking) {
+            Ok(booking) => println!("Reserved {} seat(s) with {}", booking.quantity, booking.id),
+            Err(BookingError::QuantityExceeded) => {
+                println!("Cannot reserve {} seat(s) with {}: quota exceeded", seats, booking_id)
+            }
+            Err(e) => println!("Error: {}", e),
+        }
+    }
+
     Ok(())
 }
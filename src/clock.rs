@@ -0,0 +1,90 @@
+//! Clock abstraction used by time-driven features such as the expiry sweeper.
+
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+#[cfg(feature = "std")]
+use crate::timestamp::{BookingTime, CurrentTime};
+
+/// The `Clock` trait provides the current time in the `Timestamp` type used by bookings.
+///
+/// Injecting a clock instead of reading the system time directly keeps time-based logic
+/// deterministic in tests; see `FakeClock`.
+pub trait Clock<Timestamp> {
+    fn now(&self) -> Timestamp;
+}
+
+impl<Timestamp, C: Clock<Timestamp> + ?Sized> Clock<Timestamp> for &C {
+    fn now(&self) -> Timestamp {
+        (**self).now()
+    }
+}
+
+/// A clock reading the system time.
+///
+/// Provides any `CurrentTime`, e.g. `SystemTime` or `i64` unix seconds, through `CurrentTime::now`.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl<Timestamp: CurrentTime> Clock<Timestamp> for SystemClock {
+    fn now(&self) -> Timestamp {
+        Timestamp::now()
+    }
+}
+
+/// A manually controlled clock for tests.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::clock::{Clock, FakeClock};
+///
+/// let clock = FakeClock::new(1_000_i64);
+/// clock.advance(15 * 60);
+/// assert_eq!(clock.now(), 1_900);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct FakeClock<Timestamp> {
+    now: Mutex<Timestamp>,
+}
+
+#[cfg(feature = "std")]
+impl<Timestamp: Clone> FakeClock<Timestamp> {
+    /// Creates a clock frozen at `now`.
+    pub fn new(now: Timestamp) -> Self {
+        Self { now: Mutex::new(now) }
+    }
+
+    /// Moves the clock to `now`.
+    pub fn set(&self, now: Timestamp) {
+        *self.now.lock().unwrap_or_else(|e| e.into_inner()) = now;
+    }
+
+    /// Moves the clock forward by `by`, e.g. seconds for `i64` or a `Duration` for `SystemTime`,
+    /// saturating at the current time on overflow like `advance_by`.
+    pub fn advance(&self, by: Timestamp::Duration)
+    where
+        Timestamp: BookingTime,
+    {
+        self.advance_by(&by);
+    }
+
+    /// Moves the clock forward by a `BookingTime::Duration`, saturating at the current time on overflow.
+    pub fn advance_by(&self, by: &Timestamp::Duration)
+    where
+        Timestamp: BookingTime,
+    {
+        let mut now = self.now.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(later) = now.checked_plus(by) {
+            *now = later;
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<Timestamp: Clone> Clock<Timestamp> for FakeClock<Timestamp> {
+    fn now(&self) -> Timestamp {
+        self.now.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+}
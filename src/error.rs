@@ -1,15 +1,23 @@
 //! Module for handling errors related to the booking system.
 
+use alloc::string::{String, ToString};
 use thiserror::Error;
 use crate::model::status::BookingStatus;
 
 /// Enum used to represent errors that occur during the booking process.
-#[derive(Debug, Error)]
+#[derive(Debug, Clone, Error)]
 pub enum BookingError {
     /// Error when the item with ID {0} is unavailable.
     #[error("Booking item with ID {0} is unavailable.")]
     ItemUnavailable(String),
 
+    /// Error when the item is already reserved by another active booking for an overlapping period.
+    #[error("Booking item with ID {item_id} is already reserved by booking {booking_id} for an overlapping period.")]
+    BookingConflict {
+        item_id: String,
+        booking_id: String,
+    },
+
     /// Error when the booking status is invalid, e.g., an unregistered status.
     #[error("Booking status is invalid: {0}")]
     InvalidStatus(String),
@@ -33,12 +41,71 @@ pub enum BookingError {
     #[error("Booking item quantity exceeds available limit.")]
     QuantityExceeded,
 
+    /// Error when no booking with the given ID exists in the repository.
+    #[error("Booking with ID {0} was not found.")]
+    BookingNotFound(String),
+
+    /// Error when a booking with the given ID already exists in the repository.
+    #[error("Booking with ID {0} already exists.")]
+    DuplicateBooking(String),
+
+    /// Error when a hold with the given booking ID expired before it was converted into a booking.
+    #[error("Hold {0} has expired.")]
+    HoldExpired(String),
+
+    /// Error when an idempotency key is reused for a request different from the one it was first
+    /// used for.
+    #[error("Idempotency key {0} was already used for a different booking request.")]
+    IdempotencyKeyReused(String),
+
+    /// Error when a request with the same idempotency key is still in progress.
+    #[error("Idempotency key {0} is used by a request still in progress.")]
+    IdempotencyKeyInUse(String),
+
+    /// Error when a booking was updated by someone else since it was loaded, so the update would
+    /// overwrite their change.
+    #[error("Booking with ID {booking_id} was modified concurrently: version {version} cannot replace stored version {stored}.")]
+    ConcurrentModification {
+        booking_id: String,
+        version: u64,
+        stored: u64,
+    },
+
+    /// Error when the underlying storage fails, e.g., a poisoned lock or an I/O error.
+    #[error("Storage error: {0}")]
+    StorageError(String),
+
     /// General error that doesn't fit other categories.
     #[error("General error: {0}")]
     GeneralError(String),
 }
 
 impl BookingError {
+    /// Returns a stable, machine-readable code identifying the kind of error,
+    /// e.g. `"item_unavailable"` or `"quantity_exceeded"`.
+    ///
+    /// Unlike the `Display` message, codes never change between releases and are safe
+    /// to match on in API responses and user interfaces.
+    pub fn code(&self) -> &'static str {
+        match self {
+            BookingError::ItemUnavailable(_) => "item_unavailable",
+            BookingError::BookingConflict { .. } => "booking_conflict",
+            BookingError::InvalidStatus(_) => "invalid_status",
+            BookingError::InvalidStatusTransition { .. } => "invalid_status_transition",
+            BookingError::CreationFailed(_) => "creation_failed",
+            BookingError::RuleValidationFailed(_) => "rule_validation_failed",
+            BookingError::QuantityExceeded => "quantity_exceeded",
+            BookingError::BookingNotFound(_) => "booking_not_found",
+            BookingError::DuplicateBooking(_) => "duplicate_booking",
+            BookingError::HoldExpired(_) => "hold_expired",
+            BookingError::IdempotencyKeyReused(_) => "idempotency_key_reused",
+            BookingError::IdempotencyKeyInUse(_) => "idempotency_key_in_use",
+            BookingError::ConcurrentModification { .. } => "concurrent_modification",
+            BookingError::StorageError(_) => "storage_error",
+            BookingError::GeneralError(_) => "general_error",
+        }
+    }
+
     /// Creates an InvalidStatus error with the specific status message.
     pub fn new_invalid_status(status: &str) -> Self {
         BookingError::InvalidStatus(status.to_string())
@@ -49,6 +116,14 @@ impl BookingError {
         BookingError::ItemUnavailable(item_id.to_string())
     }
 
+    /// Creates a BookingConflict error with the item ID and the ID of the conflicting booking.
+    pub fn new_booking_conflict(item_id: &str, booking_id: &str) -> Self {
+        BookingError::BookingConflict {
+            item_id: item_id.to_string(),
+            booking_id: booking_id.to_string(),
+        }
+    }
+
     /// Creates an InvalidStatusTransition error with the provided from and to status.
     ///
     /// This error is returned when an attempt is made to transition between two booking statuses
@@ -85,6 +160,46 @@ impl BookingError {
         BookingError::QuantityExceeded
     }
 
+    /// Creates a BookingNotFound error with the booking ID that could not be found.
+    pub fn new_booking_not_found(booking_id: &str) -> Self {
+        BookingError::BookingNotFound(booking_id.to_string())
+    }
+
+    /// Creates a DuplicateBooking error with the booking ID that already exists.
+    pub fn new_duplicate_booking(booking_id: &str) -> Self {
+        BookingError::DuplicateBooking(booking_id.to_string())
+    }
+
+    /// Creates a HoldExpired error with the booking ID of the expired hold.
+    pub fn new_hold_expired(booking_id: &str) -> Self {
+        BookingError::HoldExpired(booking_id.to_string())
+    }
+
+    /// Creates an IdempotencyKeyReused error with the reused key.
+    pub fn new_idempotency_key_reused(key: &str) -> Self {
+        BookingError::IdempotencyKeyReused(key.to_string())
+    }
+
+    /// Creates an IdempotencyKeyInUse error with the key of the request in progress.
+    pub fn new_idempotency_key_in_use(key: &str) -> Self {
+        BookingError::IdempotencyKeyInUse(key.to_string())
+    }
+
+    /// Creates a ConcurrentModification error with the booking ID, the version of the rejected
+    /// update and the version currently stored.
+    pub fn new_concurrent_modification(booking_id: &str, version: u64, stored: u64) -> Self {
+        BookingError::ConcurrentModification {
+            booking_id: booking_id.to_string(),
+            version,
+            stored,
+        }
+    }
+
+    /// Creates a StorageError with a specific failure message.
+    pub fn new_storage_error(message: &str) -> Self {
+        BookingError::StorageError(message.to_string())
+    }
+
     /// Creates a GeneralError with a specific message.
     pub fn new_general_error(message: &str) -> Self {
         BookingError::GeneralError(message.to_string())
@@ -0,0 +1,357 @@
+//! Domain events emitted by `BookingManager` after every successful lifecycle change,
+//! and the subscribers receiving them.
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::sync::mpsc::{self, Receiver, Sender};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::model::booking::Booking;
+use crate::model::history::StatusChange;
+use crate::model::status::{BookingStatus, Status};
+use crate::traits::Bookable;
+
+/// What happened to a booking, as carried by every `BookingEvent`.
+///
+/// # Fields
+/// - `booking_id`: ID of the booking that changed.
+/// - `item_id`: `Bookable::id()` of the booked item.
+/// - `from`: Status before the change, or `None` for a newly created booking.
+/// - `to`: Status after the change.
+/// - `at`: When the change happened: `created_at` for a new booking, and the time recorded in the
+///   status history for a transition, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BookingEventData<ID, Timestamp, S = BookingStatus> {
+    pub booking_id: ID,
+    pub item_id: String,
+
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub from: Option<S>,
+    pub to: S,
+
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub at: Option<Timestamp>,
+}
+
+/// A lifecycle change of a booking, published to a `BookingSubscriber` once it has been stored.
+///
+/// # Variants:
+///
+/// - `Created`: A booking was stored, in any initial status (`Pending`, `OnHold`, `Waitlisted`, ...).
+/// - `Confirmed`, `Canceled`, `Expired`, `Completed`, `Failed`, `CheckedIn`, `NoShow`, `Refunded`:
+///   The booking moved to that status, or to the status playing its role in a custom `Status` set.
+/// - `Modified`: Any other change, e.g. a waitlisted booking promoted to `Pending`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BookingEvent<ID, Timestamp, S = BookingStatus> {
+    Created(BookingEventData<ID, Timestamp, S>),
+    Confirmed(BookingEventData<ID, Timestamp, S>),
+    Canceled(BookingEventData<ID, Timestamp, S>),
+    Expired(BookingEventData<ID, Timestamp, S>),
+    Completed(BookingEventData<ID, Timestamp, S>),
+    Failed(BookingEventData<ID, Timestamp, S>),
+    CheckedIn(BookingEventData<ID, Timestamp, S>),
+    NoShow(BookingEventData<ID, Timestamp, S>),
+    Refunded(BookingEventData<ID, Timestamp, S>),
+    Modified(BookingEventData<ID, Timestamp, S>),
+}
+
+impl<ID, Timestamp, S> BookingEvent<ID, Timestamp, S> {
+    /// Builds the `Created` event of a newly stored booking.
+    pub fn created<T, Metadata>(booking: &Booking<T, ID, Timestamp, Metadata, S>) -> Self
+    where
+        T: Bookable,
+        ID: Clone,
+        Timestamp: Clone,
+        S: Clone,
+    {
+        BookingEvent::Created(BookingEventData {
+            booking_id: booking.id.clone(),
+            item_id: booking.item_id().to_string(),
+            from: None,
+            to: booking.status.clone(),
+            at: Some(booking.created_at.clone()),
+        })
+    }
+
+    /// Builds the event of a booking that went through `change`, choosing the variant from the
+    /// status it moved to: the variant named after the built-in status playing its role (see
+    /// `Status::from_booking_status`), or `Modified` for any other status, e.g. `Pending`.
+    pub fn transitioned<T, Metadata>(booking: &Booking<T, ID, Timestamp, Metadata, S>, change: &StatusChange<ID, Timestamp, S>) -> Self
+    where
+        T: Bookable,
+        ID: Clone,
+        Timestamp: Clone,
+        S: Status,
+    {
+        let data = BookingEventData {
+            booking_id: booking.id.clone(),
+            item_id: booking.item_id().to_string(),
+            from: Some(change.from.clone()),
+            to: change.to.clone(),
+            at: change.at.clone(),
+        };
+
+        let moved_to = |status: BookingStatus| S::from_booking_status(&status).is_some_and(|status| status == change.to);
+        if moved_to(BookingStatus::Confirmed) {
+            BookingEvent::Confirmed(data)
+        } else if moved_to(BookingStatus::Canceled) {
+            BookingEvent::Canceled(data)
+        } else if moved_to(BookingStatus::Expired) {
+            BookingEvent::Expired(data)
+        } else if moved_to(BookingStatus::Completed) {
+            BookingEvent::Completed(data)
+        } else if moved_to(BookingStatus::Failed) {
+            BookingEvent::Failed(data)
+        } else if moved_to(BookingStatus::CheckedIn) {
+            BookingEvent::CheckedIn(data)
+        } else if moved_to(BookingStatus::NoShow) {
+            BookingEvent::NoShow(data)
+        } else if moved_to(BookingStatus::Refunded) {
+            BookingEvent::Refunded(data)
+        } else {
+            BookingEvent::Modified(data)
+        }
+    }
+
+    /// Builds the event of a stored booking replaced by `current`: the event of its latest status
+    /// change if `current` has one more than `previous`, and `Modified` otherwise.
+    pub fn updated<T, Metadata>(previous: &Booking<T, ID, Timestamp, Metadata, S>, current: &Booking<T, ID, Timestamp, Metadata, S>) -> Self
+    where
+        T: Bookable,
+        ID: Clone,
+        Timestamp: Clone,
+        S: Status,
+    {
+        match current.last_change() {
+            Some(change) if current.history.len() > previous.history.len() => BookingEvent::transitioned(current, change),
+            _ => BookingEvent::Modified(BookingEventData {
+                booking_id: current.id.clone(),
+                item_id: current.item_id().to_string(),
+                from: Some(previous.status.clone()),
+                to: current.status.clone(),
+                at: None,
+            }),
+        }
+    }
+
+    /// Returns what happened to the booking.
+    pub fn data(&self) -> &BookingEventData<ID, Timestamp, S> {
+        match self {
+            BookingEvent::Created(data)
+            | BookingEvent::Confirmed(data)
+            | BookingEvent::Canceled(data)
+            | BookingEvent::Expired(data)
+            | BookingEvent::Completed(data)
+            | BookingEvent::Failed(data)
+            | BookingEvent::CheckedIn(data)
+            | BookingEvent::NoShow(data)
+            | BookingEvent::Refunded(data)
+            | BookingEvent::Modified(data) => data,
+        }
+    }
+
+    /// Returns the ID of the booking that changed.
+    pub fn booking_id(&self) -> &ID {
+        &self.data().booking_id
+    }
+
+    /// Returns the `Bookable::id()` of the booked item.
+    pub fn item_id(&self) -> &str {
+        &self.data().item_id
+    }
+}
+
+/// The `BookingSubscriber` trait receives the events of a `BookingManager`.
+///
+/// The manager calls `notify` synchronously after each successful operation, once the change has
+/// been stored, so a subscriber never sees a change that was rolled back. `()` is the default and
+/// ignores every event.
+pub trait BookingSubscriber<ID, Timestamp, S = BookingStatus> {
+    /// Handles an event. Subscribers cannot fail the operation that produced it.
+    fn notify(&self, event: &BookingEvent<ID, Timestamp, S>);
+}
+
+impl<ID, Timestamp, S> BookingSubscriber<ID, Timestamp, S> for () {
+    fn notify(&self, _event: &BookingEvent<ID, Timestamp, S>) {}
+}
+
+impl<ID, Timestamp, S, B> BookingSubscriber<ID, Timestamp, S> for Box<B>
+where
+    B: BookingSubscriber<ID, Timestamp, S> + ?Sized,
+{
+    fn notify(&self, event: &BookingEvent<ID, Timestamp, S>) {
+        (**self).notify(event)
+    }
+}
+
+/// A subscriber defined by a closure. Created with `from_fn`.
+#[derive(Debug, Clone)]
+pub struct FnSubscriber<F>(F);
+
+/// Creates a subscriber from a closure receiving each event.
+pub fn from_fn<F>(subscriber: F) -> FnSubscriber<F> {
+    FnSubscriber(subscriber)
+}
+
+impl<ID, Timestamp, S, F> BookingSubscriber<ID, Timestamp, S> for FnSubscriber<F>
+where
+    F: Fn(&BookingEvent<ID, Timestamp, S>),
+{
+    fn notify(&self, event: &BookingEvent<ID, Timestamp, S>) {
+        (self.0)(event)
+    }
+}
+
+/// A synchronous, in-process dispatcher forwarding every event to its subscribers, in the order
+/// they subscribed.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// use std::sync::{Arc, Mutex};
+/// use booking_kit::events::{from_fn, BookingEvent, EventDispatcher};
+/// use booking_kit::manager::BookingManager;
+/// use booking_kit::repository::memory::InMemoryBookingRepository;
+/// use booking_kit::traits::Bookable;
+///
+/// #[derive(Debug, Clone)]
+/// struct Room;
+/// impl Bookable for Room {
+///     fn id(&self) -> &str { "R101" }
+///     fn is_available(&self) -> bool { true }
+/// }
+///
+/// let sent = Arc::new(Mutex::new(Vec::new()));
+/// let emails = Arc::clone(&sent);
+/// let dispatcher = EventDispatcher::new().subscribe(from_fn(move |event: &BookingEvent<u32, i64>| {
+///     if let BookingEvent::Confirmed(data) = event {
+///         emails.lock().unwrap().push(data.booking_id);
+///     }
+/// }));
+///
+/// let manager = BookingManager::new(InMemoryBookingRepository::new()).with_subscriber(dispatcher);
+/// manager.try_create_booking(1, None, Room, 0_i64, None, None::<()>).unwrap();
+/// manager.confirm_booking(&1).unwrap();
+///
+/// assert_eq!(*sent.lock().unwrap(), vec![1]);
+/// # }
+/// ```
+pub struct EventDispatcher<ID, Timestamp, S = BookingStatus> {
+    subscribers: Vec<Box<dyn BookingSubscriber<ID, Timestamp, S> + Send + Sync>>,
+}
+
+impl<ID, Timestamp, S> EventDispatcher<ID, Timestamp, S> {
+    /// Creates a dispatcher without subscribers.
+    pub fn new() -> Self {
+        Self { subscribers: Vec::new() }
+    }
+
+    /// Returns the dispatcher with `subscriber` added after the existing ones.
+    pub fn subscribe<B>(mut self, subscriber: B) -> Self
+    where
+        B: BookingSubscriber<ID, Timestamp, S> + Send + Sync + 'static,
+    {
+        self.subscribers.push(Box::new(subscriber));
+        self
+    }
+
+    /// Returns the number of subscribers.
+    pub fn len(&self) -> usize {
+        self.subscribers.len()
+    }
+
+    /// Returns `true` if there are no subscribers.
+    pub fn is_empty(&self) -> bool {
+        self.subscribers.is_empty()
+    }
+}
+
+impl<ID, Timestamp, S> Default for EventDispatcher<ID, Timestamp, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<ID, Timestamp, S> fmt::Debug for EventDispatcher<ID, Timestamp, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventDispatcher")
+            .field("subscribers", &self.subscribers.len())
+            .finish()
+    }
+}
+
+impl<ID, Timestamp, S> BookingSubscriber<ID, Timestamp, S> for EventDispatcher<ID, Timestamp, S> {
+    fn notify(&self, event: &BookingEvent<ID, Timestamp, S>) {
+        for subscriber in &self.subscribers {
+            subscriber.notify(event);
+        }
+    }
+}
+
+/// A subscriber sending every event over a `std::sync::mpsc` channel, so events can be handled
+/// on another thread. Requires the `std` feature.
+///
+/// Events are dropped once the receiver is gone.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::events::{BookingEvent, ChannelSubscriber};
+/// use booking_kit::manager::BookingManager;
+/// use booking_kit::repository::memory::InMemoryBookingRepository;
+/// use booking_kit::traits::Bookable;
+///
+/// #[derive(Debug, Clone)]
+/// struct Room;
+/// impl Bookable for Room {
+///     fn id(&self) -> &str { "R101" }
+///     fn is_available(&self) -> bool { true }
+/// }
+///
+/// let (subscriber, events) = ChannelSubscriber::<u32, i64>::new();
+/// let manager = BookingManager::new(InMemoryBookingRepository::new()).with_subscriber(subscriber);
+/// manager.try_create_booking(1, None, Room, 0, None, None::<()>).unwrap();
+///
+/// let handle = std::thread::spawn(move || events.recv().unwrap());
+/// assert!(matches!(handle.join().unwrap(), BookingEvent::Created(_)));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+pub struct ChannelSubscriber<ID, Timestamp, S = BookingStatus> {
+    sender: Sender<BookingEvent<ID, Timestamp, S>>,
+}
+
+#[cfg(feature = "std")]
+impl<ID, Timestamp> ChannelSubscriber<ID, Timestamp> {
+    /// Creates a subscriber and the receiving end of its channel.
+    ///
+    /// Bookings with a custom `Status` create the channel themselves and use `from_sender`.
+    pub fn new() -> (Self, Receiver<BookingEvent<ID, Timestamp>>) {
+        let (sender, receiver) = mpsc::channel();
+        (Self { sender }, receiver)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<ID, Timestamp, S> ChannelSubscriber<ID, Timestamp, S> {
+    /// Creates a subscriber sending to an existing channel.
+    pub fn from_sender(sender: Sender<BookingEvent<ID, Timestamp, S>>) -> Self {
+        Self { sender }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<ID, Timestamp, S> BookingSubscriber<ID, Timestamp, S> for ChannelSubscriber<ID, Timestamp, S>
+where
+    ID: Clone,
+    Timestamp: Clone,
+    S: Clone,
+{
+    fn notify(&self, event: &BookingEvent<ID, Timestamp, S>) {
+        let _ = self.sender.send(event.clone());
+    }
+}
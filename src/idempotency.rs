@@ -0,0 +1,164 @@
+//! Idempotency keys letting clients safely retry booking creation.
+
+use alloc::format;
+#[cfg(feature = "std")]
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+use crate::error::BookingError;
+
+/// A creation request stored under its idempotency key.
+///
+/// # Fields
+/// - `payload`: The booking the client asked to create, compared against later requests with the
+///   same key.
+/// - `outcome`: The ID of the stored booking, or the error the request failed with. `None` while
+///   the request is in progress.
+#[derive(Debug, Clone)]
+pub struct IdempotencyRecord<ID, Payload> {
+    pub payload: Payload,
+    pub outcome: Option<Result<ID, BookingError>>,
+}
+
+/// The `IdempotencyStore` trait records creation requests and their outcome by idempotency key,
+/// for `BookingManager::try_insert_idempotent`.
+///
+/// A request first claims its key, then completes it with its outcome, or releases it if it
+/// should be retried. `()` is the default and has no store: idempotent creation fails.
+pub trait IdempotencyStore<ID, Payload> {
+    /// Claims `key` for a request with `payload` if no record exists for it, and returns `None`.
+    /// Otherwise returns the existing record and leaves it unchanged.
+    fn claim(&self, key: &str, payload: &Payload) -> Result<Option<IdempotencyRecord<ID, Payload>>, BookingError>;
+
+    /// Records the outcome of the request that claimed `key`.
+    fn complete(&self, key: &str, outcome: Result<ID, BookingError>) -> Result<(), BookingError>;
+
+    /// Removes the claim on `key` without recording an outcome, so the request can run again.
+    fn release(&self, key: &str) -> Result<(), BookingError>;
+}
+
+impl<ID, Payload> IdempotencyStore<ID, Payload> for () {
+    fn claim(&self, key: &str, _payload: &Payload) -> Result<Option<IdempotencyRecord<ID, Payload>>, BookingError> {
+        Err(BookingError::new_creation_failed(&format!("no idempotency store configured for key {key}")))
+    }
+
+    fn complete(&self, _key: &str, _outcome: Result<ID, BookingError>) -> Result<(), BookingError> {
+        Ok(())
+    }
+
+    fn release(&self, _key: &str) -> Result<(), BookingError> {
+        Ok(())
+    }
+}
+
+/// A thread-safe in-memory `IdempotencyStore`. Requires the `std` feature.
+///
+/// Keys are kept until `forget` is called; applications with many clients should forget keys
+/// once clients can no longer retry, e.g. after a day.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::idempotency::InMemoryIdempotencyStore;
+/// use booking_kit::manager::BookingManager;
+/// use booking_kit::repository::memory::InMemoryBookingRepository;
+/// use booking_kit::traits::Bookable;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Room;
+/// impl Bookable for Room {
+///     fn id(&self) -> &str { "R101" }
+///     fn is_available(&self) -> bool { true }
+/// }
+///
+/// let manager = BookingManager::new(InMemoryBookingRepository::new())
+///     .with_idempotency(InMemoryIdempotencyStore::new());
+/// let booking = BookingManager::create(1, None, Room, 0_i64, None, None::<()>);
+///
+/// manager.try_insert_idempotent("request-1", booking.clone()).unwrap();
+/// // The client timed out and retries: the booking is returned instead of a duplicate error.
+/// assert_eq!(manager.try_insert_idempotent("request-1", booking).unwrap().id, 1);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct InMemoryIdempotencyStore<ID, Payload> {
+    records: Mutex<HashMap<String, IdempotencyRecord<ID, Payload>>>,
+}
+
+#[cfg(feature = "std")]
+impl<ID, Payload> InMemoryIdempotencyStore<ID, Payload> {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self { records: Mutex::new(HashMap::new()) }
+    }
+
+    /// Returns the record stored under `key`, if any.
+    pub fn get(&self, key: &str) -> Result<Option<IdempotencyRecord<ID, Payload>>, BookingError>
+    where
+        ID: Clone,
+        Payload: Clone,
+    {
+        let records = self.records.lock().map_err(|e| BookingError::new_storage_error(&e.to_string()))?;
+        Ok(records.get(key).cloned())
+    }
+
+    /// Removes the record stored under `key`. Returns `true` if there was one.
+    pub fn forget(&self, key: &str) -> Result<bool, BookingError> {
+        let mut records = self.records.lock().map_err(|e| BookingError::new_storage_error(&e.to_string()))?;
+        Ok(records.remove(key).is_some())
+    }
+
+    /// Returns the number of stored keys.
+    pub fn len(&self) -> usize {
+        self.records.lock().map(|records| records.len()).unwrap_or(0)
+    }
+
+    /// Returns `true` if no keys are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<ID, Payload> Default for InMemoryIdempotencyStore<ID, Payload> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<ID, Payload> IdempotencyStore<ID, Payload> for InMemoryIdempotencyStore<ID, Payload>
+where
+    ID: Clone,
+    Payload: Clone,
+{
+    fn claim(&self, key: &str, payload: &Payload) -> Result<Option<IdempotencyRecord<ID, Payload>>, BookingError> {
+        let mut records = self.records.lock().map_err(|e| BookingError::new_storage_error(&e.to_string()))?;
+        if let Some(record) = records.get(key) {
+            return Ok(Some(record.clone()));
+        }
+        records.insert(
+            key.to_string(),
+            IdempotencyRecord {
+                payload: payload.clone(),
+                outcome: None,
+            },
+        );
+        Ok(None)
+    }
+
+    fn complete(&self, key: &str, outcome: Result<ID, BookingError>) -> Result<(), BookingError> {
+        let mut records = self.records.lock().map_err(|e| BookingError::new_storage_error(&e.to_string()))?;
+        if let Some(record) = records.get_mut(key) {
+            record.outcome = Some(outcome);
+        }
+        Ok(())
+    }
+
+    fn release(&self, key: &str) -> Result<(), BookingError> {
+        let mut records = self.records.lock().map_err(|e| BookingError::new_storage_error(&e.to_string()))?;
+        records.remove(key);
+        Ok(())
+    }
+}
@@ -27,12 +27,42 @@
 //! - **BookingManager** — Handles your booking lifecycle—create, confirm, cancel, or expire your bookings.
 //! - **Booking** — A flexible struct to hold booking details, like ID, status, and the item you're booking.
 //! - **BookingStatus** — A friendly enum to manage all the possible states your booking can be in.
+//! - **TransitionPolicy** — A configurable status transition table, loadable from JSON.
+//! - **BookingRepository** — A pluggable storage trait, with thread-safe in-memory and append-only JSON-lines file implementations.
+//! - **BookingTime** — Ordering and duration arithmetic for timestamps (`i64`, `SystemTime`, and optionally chrono, time, jiff).
+//! - **BookingRule** — Composable business rules (lead time, advance window, per-user limits, blackouts).
+//! - **ValidationReport** — Collects every reason a booking was rejected, with machine-readable codes.
+//! - **ExpirySweeper** — Expires overdue `Pending` bookings and holds using an injectable `Clock`.
+//! - **MultiBooking** — Groups several line items (flight, hotel, car) reserved all-or-nothing, each of which can be confirmed or canceled on its own.
+//! - **Waitlist** — Queues bookings for taken items (FIFO or by priority) and promotes them when the item frees up.
+//! - **Holds** — `try_hold` locks inventory for a TTL during checkout; `convert_hold` turns it into a booking.
+//! - **BookingEvent** — Emitted to a `BookingSubscriber` after every successful operation, synchronously or over a channel.
+//! - **BookingAggregate** — Event-sourced booking rebuilt from an append-only log, with snapshots and point-in-time replay.
+//! - **Outbox** — Stores each booking write with its event and relays them to a publisher with at-least-once delivery.
+//! - **Idempotency** — `try_insert_idempotent` replays the original outcome when a client retries with the same idempotency key.
+//! - **Versions** — Each status transition bumps `Booking::version`; stale writes fail with `ConcurrentModification` and can be retried with `retry_on_conflict`.
+//! - **TypedBooking** — Optional typestate wrapper (`TypedBooking<Pending, ...>`) whose transition methods only exist for legal moves, converting losslessly to and from `Booking`.
+//! - **Check-in & No-shows** — `CheckedIn`, `NoShow` and `Refunded` statuses with matching `check_in_booking`, `mark_no_show_booking` and `refund_booking` operations.
+//! - **Custom Statuses** — Implement the `Status` trait for your own status enum and pass its `TransitionPolicy` to `with_policy`; `BookingStatus` remains the default.
 //! - **Error Types** — Robust error handling for any booking mishaps.
 //! ---
+//!
+//! ## ⚙️ Cargo Features
+//!
+//! - `std` (default) — Enables the in-memory and file repositories, `Waitlist`, `FakeClock`, `SystemClock`, `ChannelSubscriber`, `DeduplicatingPublisher`, `InMemoryIdempotencyStore` and `CurrentTime`. Without it the crate is `#![no_std]` and only needs `alloc`; `cargo no-std-check` builds `ci/no_std` for `thumbv7em-none-eabihf` to check this.
+//! - `serde` (default) — Derives `Serialize` and `Deserialize` for bookings, events and the other data types.
+//! - `json` (default) — Enables `serde_json` for `TransitionPolicy::from_json` and the file-backed repositories.
+//! - `chrono` — Implements `BookingTime` for `chrono::DateTime<Utc>`.
+//! - `time` — Implements `BookingTime` for `time::OffsetDateTime`.
+//! - `jiff` — Implements `BookingTime` for `jiff::Timestamp`.
+//! - `sqlite` — Adds `repository::sqlite::SqliteBookingRepository`, storing bookings in SQLite (bundled) with transactional status transitions.
+//!
+//! ---
 //! 
 //! ## 💡 Quick Start
 //!
-//! ```rust
+#![cfg_attr(feature = "json", doc = "```rust")]
+#![cfg_attr(not(feature = "json"), doc = "```ignore")]
 //! use serde::{Deserialize, Serialize};
 //! use booking_kit::manager::BookingManager;
 //! use booking_kit::traits::Bookable;
@@ -101,9 +131,28 @@
 //!
 //! ---
 
+#![cfg_attr(not(feature = "std"), no_std)]
+// `Booking` carries five type parameters, so repository signatures returning collections of
+// bookings exceed clippy's default type complexity threshold.
+#![allow(clippy::type_complexity)]
+
+extern crate alloc;
+
 /// Core data model definitions for booking operations.
 pub mod model;
 pub mod manager;
 pub mod traits;
 pub mod error;
+pub mod repository;
+pub mod policy;
+pub mod clock;
+pub mod timestamp;
+pub mod rules;
+pub mod validation;
+pub mod sweeper;
+pub mod waitlist;
+pub mod events;
+pub mod sourcing;
+pub mod outbox;
+pub mod idempotency;
 
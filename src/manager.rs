@@ -1,13 +1,62 @@
-//! Stateless manager providing booking logic APIs.
+//! Booking manager providing booking logic APIs.
+//!
+//! `BookingManager` offers two flavours of the same lifecycle:
+//!
+//! - Stateless associated functions (`BookingManager::create`, `BookingManager::confirm`, ...)
+//!   that operate on a `Booking` the caller already holds.
+//! - Repository-backed methods (`create_booking`, `confirm_booking`, ...) available on a manager
+//!   constructed around a `BookingRepository` with `BookingManager::new`, which load, transition
+//!   and persist a booking in one call.
+//!
+//! Every status change is validated through a `TransitionPolicy`: the stateless functions use
+//! `TransitionPolicy::default()`, while a constructed manager uses the policy given to `with_policy`.
+//! Passing a policy over a custom `Status` set also switches the manager to bookings in that set.
 
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::marker::PhantomData;
 use crate::error::BookingError;
+use crate::events::{BookingEvent, BookingSubscriber};
+use crate::idempotency::IdempotencyStore;
 use crate::model::booking::Booking;
-use crate::model::status::BookingStatus;
+use crate::model::history::ChangeContext;
+use crate::model::multi::MultiBooking;
+use crate::model::status::{BookingStatus, Status};
+use crate::policy::{Transition, TransitionPolicy};
+use crate::repository::BookingRepository;
+use crate::rules::{BookingRule, RuleContext};
+use crate::validation::ValidationReport;
+use crate::waitlist::{BookingWaitlist, PROMOTION_REASON};
+use crate::sweeper::EXPIRY_REASON;
+use crate::timestamp::BookingTime;
 use crate::traits::Bookable;
 
 /// `BookingManager` is a struct that manages the booking process, including handling reservations,
 /// validating data, and interacting with available resources.
-pub struct BookingManager;
+///
+/// # Type Parameters
+/// - `R`: The repository used by the repository-backed methods. Defaults to `()` for the
+///   stateless associated functions.
+/// - `Q`: The `BookingRule` evaluated on creation and on every transition. Defaults to `()`,
+///   which accepts every booking.
+/// - `W`: The `BookingWaitlist` bookings are queued on when their item is taken. Defaults to
+///   `()`, which has no waitlist.
+/// - `E`: The `BookingSubscriber` notified after every successful operation. Defaults to `()`,
+///   which ignores every event.
+/// - `K`: The `IdempotencyStore` used by `try_insert_idempotent`. Defaults to `()`, which has no
+///   store.
+/// - `S`: The `Status` set of the managed bookings. Defaults to `BookingStatus`; set by `with_policy`.
+#[derive(Debug, Clone)]
+pub struct BookingManager<R = (), Q = (), W = (), E = (), K = (), S = BookingStatus> {
+    repository: R,
+    policy: TransitionPolicy<S>,
+    rules: Q,
+    waitlist: W,
+    subscriber: E,
+    idempotency: K,
+}
 
 impl BookingManager {
     /// Creates a new booking with status `Pending`, without checking item availability.
@@ -24,21 +73,49 @@ impl BookingManager {
     where
         T: Bookable,
         ID: Clone,
+    {
+        BookingManager::create_with_status(booking_id, user_id, item, BookingStatus::Pending, created_at, expires_at, metadata)
+    }
+
+    /// Creates a new booking in the given initial `status`, without checking item availability.
+    ///
+    /// Use this to create bookings with a custom `Status` set, whose initial status
+    /// `BookingManager::create` cannot know.
+    pub fn create_with_status<T, ID, Timestamp, Metadata, S>(
+        booking_id: ID,
+        user_id: Option<ID>,
+        item: T,
+        status: S,
+        created_at: Timestamp,
+        expires_at: Option<Timestamp>,
+        metadata: Option<Metadata>,
+    ) -> Booking<T, ID, Timestamp, Metadata, S>
+    where
+        T: Bookable,
     {
         Booking {
             id: booking_id,
             user_id,
             item,
-            status: BookingStatus::Pending,
+            quantity: 1,
+            status,
             created_at,
             expires_at,
+            period: None,
             metadata,
+            history: Vec::new(),
+            version: 0,
         }
     }
 
     /// Attempts to create a booking after checking item availability via `Bookable::is_available()`.
     ///
     /// Returns `BookingError::ItemUnavailable` if the item is not available.
+    ///
+    /// Overlaps with other bookings of the same item are not checked here since the stateless API
+    /// has no view of them; use `BookingManager::try_create_against` with the bookings you hold,
+    /// or the repository-backed `try_create_booking` / `try_insert` which perform the check
+    /// automatically.
     pub fn try_create<T, ID, Timestamp, Metadata>(
         booking_id: ID,
         user_id: Option<ID>,
@@ -59,44 +136,1559 @@ impl BookingManager {
             id: booking_id,
             user_id,
             item,
+            quantity: 1,
             status: BookingStatus::Pending,
             created_at,
             expires_at,
+            period: None,
             metadata,
+            history: Vec::new(),
+            version: 0,
         })
     }
 
-    /// Marks an existing booking as `Confirmed`.
+    /// Accepts `candidate`, typically built with `create` and `with_period`, after checking it
+    /// against the caller's view of the other bookings in `existing`.
     ///
-    /// Typically used when payment or approval is completed.
-    pub fn confirm<T, ID, Timestamp, Metadata>(
-        booking: &mut Booking<T, ID, Timestamp, Metadata>,
+    /// Returns `BookingError::ItemUnavailable` if the item is not available, and otherwise the
+    /// errors of `check_capacity` for items with a capacity or of `check_conflicts` for items
+    /// without one, so the stateless API rejects double bookings like `try_insert` does.
+    pub fn try_create_against<T, ID, Timestamp, Metadata, S>(
+        candidate: Booking<T, ID, Timestamp, Metadata, S>,
+        existing: &[Booking<T, ID, Timestamp, Metadata, S>],
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        S: Status,
+        T: Bookable,
+        ID: PartialEq + Display,
+        Timestamp: PartialOrd,
+    {
+        if !candidate.item.is_available() {
+            return Err(BookingError::new_item_unavailable(candidate.item_id()));
+        }
+        check_fits(&candidate, existing)?;
+        Ok(candidate)
+    }
+
+    /// Checks that `candidate` does not overlap any active booking of the same item in `existing`.
+    ///
+    /// Holds whose `expires_at` is not after the candidate's `created_at` are ignored.
+    /// Returns `BookingError::BookingConflict` carrying the ID of the first conflicting booking,
+    /// or `BookingError::CreationFailed` if the candidate's period does not end after it starts.
+    pub fn check_conflicts<T, ID, Timestamp, Metadata, S>(
+        candidate: &Booking<T, ID, Timestamp, Metadata, S>,
+        existing: &[Booking<T, ID, Timestamp, Metadata, S>],
     ) -> Result<(), BookingError>
     where
+        S: Status,
         T: Bookable,
+        ID: PartialEq + Display,
+        Timestamp: PartialOrd,
     {
-        let from = booking.status.clone();
-        let to = BookingStatus::Confirmed;
+        ensure_valid_period(candidate)?;
 
-        if !from.can_transition_to(&to) {
-            return Err(BookingError::new_invalid_transition(from, to));
+        match existing
+            .iter()
+            .find(|other| other.id != candidate.id && other.holds_item_at(&candidate.created_at) && candidate.overlaps(*other))
+        {
+            Some(conflict) => Err(BookingError::new_booking_conflict(
+                candidate.item_id(),
+                &conflict.id.to_string(),
+            )),
+            None => Ok(()),
         }
+    }
+
+    /// Checks that reserving `candidate` does not exceed the capacity of its item, given the
+    /// active bookings in `existing` that share that capacity.
+    ///
+    /// Returns `BookingError::QuantityExceeded` if the summed quantities would exceed
+    /// `Bookable::capacity()`, and `BookingError::CreationFailed` for a zero quantity or an invalid period.
+    /// Items without a capacity are not limited by this check. Holds whose `expires_at` is not after
+    /// the candidate's `created_at` are ignored.
+    pub fn check_capacity<T, ID, Timestamp, Metadata, S>(
+        candidate: &Booking<T, ID, Timestamp, Metadata, S>,
+        existing: &[Booking<T, ID, Timestamp, Metadata, S>],
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+        ID: PartialEq,
+        Timestamp: PartialOrd,
+    {
+        if candidate.quantity == 0 {
+            return Err(BookingError::new_creation_failed("booking quantity must be at least 1"));
+        }
+        ensure_valid_period(candidate)?;
+
+        let Some(capacity) = candidate.item.capacity() else {
+            return Ok(());
+        };
+
+        let reserved: u64 = existing
+            .iter()
+            .filter(|other| other.id != candidate.id && other.holds_item_at(&candidate.created_at) && candidate.shares_capacity_with(*other))
+            .map(|other| u64::from(other.quantity))
+            .sum();
+
+        if reserved + u64::from(candidate.quantity) > u64::from(capacity) {
+            return Err(BookingError::new_quantity_exceeded());
+        }
+
+        Ok(())
+    }
+
+    /// Creates a multi-item booking from lines built with `BookingManager::create`, checking every
+    /// line before accepting any of them.
+    ///
+    /// Returns `BookingError::ItemUnavailable` listing every unavailable item ID (comma-separated),
+    /// `BookingError::CreationFailed` if there are no lines, or the first capacity or conflict
+    /// error between the lines themselves.
+    pub fn try_create_multi<T, ID, Timestamp, Metadata>(
+        booking_id: ID,
+        user_id: Option<ID>,
+        lines: Vec<Booking<T, ID, Timestamp, Metadata>>,
+    ) -> Result<MultiBooking<T, ID, Timestamp, Metadata>, BookingError>
+    where
+        T: Bookable,
+        ID: PartialEq + Display,
+        Timestamp: PartialOrd,
+    {
+        create_multi(booking_id, user_id, lines)
+    }
+
+    /// Moves one line of a multi-item booking to the `to` status if `policy` allows it, leaving
+    /// the other lines untouched.
+    ///
+    /// Returns `BookingError::BookingNotFound` if the booking has no line with the given ID, and
+    /// `BookingError::InvalidStatusTransition` if the transition is not allowed.
+    pub fn transition_line_with<T, ID, Timestamp, Metadata, S>(
+        policy: &TransitionPolicy<S>,
+        multi: &mut MultiBooking<T, ID, Timestamp, Metadata, S>,
+        line_id: &ID,
+        to: S,
+        context: ChangeContext<ID, Timestamp>,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+        ID: PartialEq + Display,
+    {
+        let line = multi
+            .line_mut(line_id)
+            .ok_or_else(|| BookingError::new_booking_not_found(&line_id.to_string()))?;
+        BookingManager::transition_with(policy, line, to, context)
+    }
+
+    /// Marks one line of a multi-item booking as `Confirmed`.
+    pub fn confirm_line<T, ID, Timestamp, Metadata, S>(
+        multi: &mut MultiBooking<T, ID, Timestamp, Metadata, S>,
+        line_id: &ID,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+        ID: PartialEq + Display,
+    {
+        let to = S::try_from_booking_status(&BookingStatus::Confirmed)?;
+        BookingManager::transition_line_with(&TransitionPolicy::default(), multi, line_id, to, ChangeContext::new())
+    }
 
-        booking.status = to;
+    /// Marks one line of a multi-item booking as `Canceled`, e.g. to drop the rental car from a
+    /// trip while the flight and hotel stay booked.
+    pub fn cancel_line<T, ID, Timestamp, Metadata, S>(
+        multi: &mut MultiBooking<T, ID, Timestamp, Metadata, S>,
+        line_id: &ID,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+        ID: PartialEq + Display,
+    {
+        let to = S::try_from_booking_status(&BookingStatus::Canceled)?;
+        BookingManager::transition_line_with(&TransitionPolicy::default(), multi, line_id, to, ChangeContext::new())
+    }
+
+    /// Moves a booking to the `to` status if `policy` allows it, records the change described by
+    /// `context` in the booking's history, and increments its `version`, so the result can be
+    /// stored with `BookingRepository::update`.
+    ///
+    /// Returns `BookingError::InvalidStatusTransition` if the transition is not allowed,
+    /// leaving the booking unchanged.
+    pub fn transition_with<T, ID, Timestamp, Metadata, S>(
+        policy: &TransitionPolicy<S>,
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+        to: S,
+        context: ChangeContext<ID, Timestamp>,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+    {
+        policy.validate(&booking.status, &to)?;
+        let from = core::mem::replace(&mut booking.status, to.clone());
+        booking.history.push(context.into_change(from, to));
+        booking.version += 1;
         Ok(())
     }
 
+    /// Marks an existing booking as `Confirmed`.
+    ///
+    /// Typically used when payment or approval is completed.
+    pub fn confirm<T, ID, Timestamp, Metadata, S>(
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+    {
+        let to = S::try_from_booking_status(&BookingStatus::Confirmed)?;
+        BookingManager::transition_with(&TransitionPolicy::default(), booking, to, ChangeContext::new())
+    }
+
     /// Marks an existing booking as `Canceled`.
     ///
     /// Can be used due to user request, timeout, or manual rejection.
-    pub fn cancel<T, ID, Timestamp, Metadata>(booking: &mut Booking<T, ID, Timestamp, Metadata>) {
-        booking.status = BookingStatus::Canceled;
+    /// Returns `BookingError::InvalidStatusTransition` if the booking can no longer be canceled,
+    /// e.g. because it is already `Completed`.
+    pub fn try_cancel<T, ID, Timestamp, Metadata, S>(
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+    {
+        let to = S::try_from_booking_status(&BookingStatus::Canceled)?;
+        BookingManager::transition_with(&TransitionPolicy::default(), booking, to, ChangeContext::new())
     }
 
     /// Marks an existing booking as `Expired`.
     ///
     /// Should be used when the booking is no longer valid due to time constraints or policies.
+    /// Returns `BookingError::InvalidStatusTransition` if the booking is not awaiting confirmation.
+    pub fn try_expire<T, ID, Timestamp, Metadata, S>(
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+    {
+        let to = S::try_from_booking_status(&BookingStatus::Expired)?;
+        BookingManager::transition_with(&TransitionPolicy::default(), booking, to, ChangeContext::new())
+    }
+
+    /// Marks an existing booking as `Completed`.
+    ///
+    /// Typically used once the reserved item has been used, e.g. after check-out or the event took place.
+    pub fn complete<T, ID, Timestamp, Metadata, S>(
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+    {
+        let to = S::try_from_booking_status(&BookingStatus::Completed)?;
+        BookingManager::transition_with(&TransitionPolicy::default(), booking, to, ChangeContext::new())
+    }
+
+    /// Marks an existing booking as `Failed`.
+    ///
+    /// Typically used when payment or an upstream reservation could not be completed.
+    pub fn fail<T, ID, Timestamp, Metadata, S>(
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+    {
+        let to = S::try_from_booking_status(&BookingStatus::Failed)?;
+        BookingManager::transition_with(&TransitionPolicy::default(), booking, to, ChangeContext::new())
+    }
+
+    /// Marks a confirmed booking as `CheckedIn` once the guest has arrived.
+    ///
+    /// The booking keeps holding its item until it is completed.
+    pub fn check_in<T, ID, Timestamp, Metadata, S>(
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+    {
+        let to = S::try_from_booking_status(&BookingStatus::CheckedIn)?;
+        BookingManager::transition_with(&TransitionPolicy::default(), booking, to, ChangeContext::new())
+    }
+
+    /// Marks a confirmed booking as `NoShow` when the guest never arrived, releasing its item.
+    pub fn mark_no_show<T, ID, Timestamp, Metadata, S>(
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+    {
+        let to = S::try_from_booking_status(&BookingStatus::NoShow)?;
+        BookingManager::transition_with(&TransitionPolicy::default(), booking, to, ChangeContext::new())
+    }
+
+    /// Marks a confirmed booking as `Refunded` when it is called off and its payment returned.
+    pub fn refund<T, ID, Timestamp, Metadata, S>(
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), BookingError>
+    where
+        S: Status,
+        T: Bookable,
+    {
+        let to = S::try_from_booking_status(&BookingStatus::Refunded)?;
+        BookingManager::transition_with(&TransitionPolicy::default(), booking, to, ChangeContext::new())
+    }
+
+    /// Marks an existing booking as `Canceled`, without validating the transition.
+    #[deprecated(since = "0.2.0", note = "use `BookingManager::try_cancel`, which validates the transition")]
+    pub fn cancel<T, ID, Timestamp, Metadata>(booking: &mut Booking<T, ID, Timestamp, Metadata>) {
+        let from = core::mem::replace(&mut booking.status, BookingStatus::Canceled);
+        booking.history.push(ChangeContext::new().into_change(from, BookingStatus::Canceled));
+        booking.version += 1;
+    }
+
+    /// Marks an existing booking as `Expired`, without validating the transition.
+    #[deprecated(since = "0.2.0", note = "use `BookingManager::try_expire`, which validates the transition")]
     pub fn expire<T, ID, Timestamp, Metadata>(booking: &mut Booking<T, ID, Timestamp, Metadata>) {
-        booking.status = BookingStatus::Expired;
+        let from = core::mem::replace(&mut booking.status, BookingStatus::Expired);
+        booking.history.push(ChangeContext::new().into_change(from, BookingStatus::Expired));
+        booking.version += 1;
+    }
+
+    /// Runs `operation` up to `max_attempts` times (at least once) while it fails with
+    /// `BookingError::ConcurrentModification`, and returns its first other outcome.
+    ///
+    /// Repository-backed operations reload the booking on every call, so retrying applies the
+    /// change on top of the concurrent one, after checking the transition again. Returns the last
+    /// conflict if every attempt failed.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "std")] {
+    /// use booking_kit::manager::BookingManager;
+    /// use booking_kit::repository::memory::InMemoryBookingRepository;
+    /// use booking_kit::traits::Bookable;
+    ///
+    /// #[derive(Debug, Clone)]
+    /// struct Room;
+    /// impl Bookable for Room {
+    ///     fn id(&self) -> &str { "R101" }
+    ///     fn is_available(&self) -> bool { true }
+    /// }
+    ///
+    /// let manager = BookingManager::new(InMemoryBookingRepository::new());
+    /// manager.try_create_booking(1, None, Room, 0_i64, None, None::<()>).unwrap();
+    ///
+    /// let confirmed = BookingManager::retry_on_conflict(3, || manager.confirm_booking(&1)).unwrap();
+    /// assert_eq!(confirmed.version, 1);
+    /// # }
+    /// ```
+    pub fn retry_on_conflict<O, F>(max_attempts: u32, mut operation: F) -> Result<O, BookingError>
+    where
+        F: FnMut() -> Result<O, BookingError>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation() {
+                Err(BookingError::ConcurrentModification { .. }) if attempt < max_attempts => attempt += 1,
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Checks and builds a multi-item booking in any status set; see `BookingManager::try_create_multi`.
+fn create_multi<T, ID, Timestamp, Metadata, S>(
+    booking_id: ID,
+    user_id: Option<ID>,
+    lines: Vec<Booking<T, ID, Timestamp, Metadata, S>>,
+) -> Result<MultiBooking<T, ID, Timestamp, Metadata, S>, BookingError>
+where
+    S: Status,
+    T: Bookable,
+    ID: PartialEq + Display,
+    Timestamp: PartialOrd,
+{
+    if lines.is_empty() {
+        return Err(BookingError::new_creation_failed("multi-item booking must contain at least one line"));
+    }
+
+    let unavailable: Vec<&str> = lines
+        .iter()
+        .filter(|line| !line.item.is_available())
+        .map(|line| line.item_id())
+        .collect();
+    if !unavailable.is_empty() {
+        return Err(BookingError::new_item_unavailable(&unavailable.join(", ")));
+    }
+
+    for (index, line) in lines.iter().enumerate() {
+        check_fits(line, &lines[..index])?;
+    }
+
+    Ok(MultiBooking {
+        id: booking_id,
+        user_id,
+        lines,
+    })
+}
+
+/// Checks `booking` against `existing` ones: capacity for items with a `Bookable::capacity()`,
+/// overlapping periods otherwise.
+fn check_fits<T, ID, Timestamp, Metadata, S>(
+    booking: &Booking<T, ID, Timestamp, Metadata, S>,
+    existing: &[Booking<T, ID, Timestamp, Metadata, S>],
+) -> Result<(), BookingError>
+where
+    S: Status,
+    T: Bookable,
+    ID: PartialEq + Display,
+    Timestamp: PartialOrd,
+{
+    match booking.item.capacity() {
+        Some(_) => BookingManager::check_capacity(booking, existing),
+        None => BookingManager::check_conflicts(booking, existing),
+    }
+}
+
+/// Splits the bookings of `known` sharing the item of `booking` and those sharing its user.
+fn related_bookings<T, ID, Timestamp, Metadata, S>(
+    booking: &Booking<T, ID, Timestamp, Metadata, S>,
+    known: &[Booking<T, ID, Timestamp, Metadata, S>],
+) -> (Vec<Booking<T, ID, Timestamp, Metadata, S>>, Vec<Booking<T, ID, Timestamp, Metadata, S>>)
+where
+    T: Bookable + Clone,
+    ID: PartialEq + Clone,
+    Timestamp: Clone,
+    Metadata: Clone,
+    S: Clone,
+{
+    let item_bookings = known.iter().filter(|other| other.item_id() == booking.item_id()).cloned().collect();
+    let user_bookings = match &booking.user_id {
+        Some(user_id) => known.iter().filter(|other| other.user_id.as_ref() == Some(user_id)).cloned().collect(),
+        None => Vec::new(),
+    };
+    (item_bookings, user_bookings)
+}
+
+fn ensure_valid_period<T, ID, Timestamp, Metadata, S>(
+    booking: &Booking<T, ID, Timestamp, Metadata, S>,
+) -> Result<(), BookingError>
+where
+    Timestamp: PartialOrd,
+{
+    if booking.period.as_ref().is_some_and(|period| !period.is_valid()) {
+        return Err(BookingError::new_creation_failed("booking period must end after it starts"));
+    }
+    Ok(())
+}
+
+/// Releases the claim on `key` when dropped unless `disarm`ed, so a request that panics after
+/// claiming its key does not leave it in use for good.
+struct ClaimGuard<'a, K, ID, Payload>
+where
+    K: IdempotencyStore<ID, Payload>,
+{
+    store: &'a K,
+    key: &'a str,
+    armed: bool,
+    marker: PhantomData<fn(ID, Payload)>,
+}
+
+impl<'a, K, ID, Payload> ClaimGuard<'a, K, ID, Payload>
+where
+    K: IdempotencyStore<ID, Payload>,
+{
+    fn new(store: &'a K, key: &'a str) -> Self {
+        Self { store, key, armed: true, marker: PhantomData }
+    }
+
+    fn disarm(mut self) {
+        self.armed = false;
+    }
+}
+
+impl<K, ID, Payload> Drop for ClaimGuard<'_, K, ID, Payload>
+where
+    K: IdempotencyStore<ID, Payload>,
+{
+    fn drop(&mut self) {
+        if self.armed {
+            let _ = self.store.release(self.key);
+        }
+    }
+}
+
+impl<R> BookingManager<R> {
+    /// Creates a manager around the given repository, using the default `TransitionPolicy`
+    /// and no booking rules.
+    pub fn new(repository: R) -> Self {
+        Self {
+            repository,
+            policy: TransitionPolicy::default(),
+            rules: (),
+            waitlist: (),
+            subscriber: (),
+            idempotency: (),
+        }
+    }
+}
+
+impl<R, Q, W, E, K, S> Default for BookingManager<R, Q, W, E, K, S>
+where
+    R: Default,
+    Q: Default,
+    W: Default,
+    E: Default,
+    K: Default,
+    S: Status,
+{
+    fn default() -> Self {
+        Self {
+            repository: R::default(),
+            policy: TransitionPolicy::default(),
+            rules: Q::default(),
+            waitlist: W::default(),
+            subscriber: E::default(),
+            idempotency: K::default(),
+        }
+    }
+}
+
+impl<R, Q, W, E, K, S> BookingManager<R, Q, W, E, K, S> {
+    /// Returns the manager with `rules` evaluated on creation and on every transition.
+    ///
+    /// Combine several rules with `rules::All`, `rules::Any` and `rules::Not`.
+    pub fn with_rules<Rules>(self, rules: Rules) -> BookingManager<R, Rules, W, E, K, S> {
+        BookingManager {
+            repository: self.repository,
+            policy: self.policy,
+            rules,
+            waitlist: self.waitlist,
+            subscriber: self.subscriber,
+            idempotency: self.idempotency,
+        }
+    }
+
+    /// Returns the rules evaluated by this manager.
+    pub fn rules(&self) -> &Q {
+        &self.rules
+    }
+
+    /// Returns the manager with `waitlist` used by `try_create_or_waitlist`, and promoted from
+    /// whenever an active booking releases its item.
+    pub fn with_waitlist<L>(self, waitlist: L) -> BookingManager<R, Q, L, E, K, S> {
+        BookingManager {
+            repository: self.repository,
+            policy: self.policy,
+            rules: self.rules,
+            waitlist,
+            subscriber: self.subscriber,
+            idempotency: self.idempotency,
+        }
+    }
+
+    /// Returns the waitlist of this manager.
+    pub fn waitlist(&self) -> &W {
+        &self.waitlist
+    }
+
+    /// Returns the manager with `subscriber` notified of a `BookingEvent` after every successful
+    /// operation. Use `events::EventDispatcher` to notify several subscribers.
+    pub fn with_subscriber<Subscriber>(self, subscriber: Subscriber) -> BookingManager<R, Q, W, Subscriber, K, S> {
+        BookingManager {
+            repository: self.repository,
+            policy: self.policy,
+            rules: self.rules,
+            waitlist: self.waitlist,
+            subscriber,
+            idempotency: self.idempotency,
+        }
+    }
+
+    /// Returns the subscriber notified by this manager.
+    pub fn subscriber(&self) -> &E {
+        &self.subscriber
+    }
+
+    /// Returns the manager with `store` recording the outcome of `try_insert_idempotent` calls.
+    pub fn with_idempotency<Store>(self, store: Store) -> BookingManager<R, Q, W, E, Store, S> {
+        BookingManager {
+            repository: self.repository,
+            policy: self.policy,
+            rules: self.rules,
+            waitlist: self.waitlist,
+            subscriber: self.subscriber,
+            idempotency: store,
+        }
+    }
+
+    /// Returns the idempotency store of this manager.
+    pub fn idempotency(&self) -> &K {
+        &self.idempotency
+    }
+
+    /// Returns the manager with its transitions validated through `policy`.
+    ///
+    /// The manager works with bookings in the status set of the policy, so passing e.g.
+    /// `TransitionPolicy::<VisitStatus>::default()` switches it to a custom `Status` set.
+    pub fn with_policy<P>(self, policy: TransitionPolicy<P>) -> BookingManager<R, Q, W, E, K, P> {
+        BookingManager {
+            repository: self.repository,
+            policy,
+            rules: self.rules,
+            waitlist: self.waitlist,
+            subscriber: self.subscriber,
+            idempotency: self.idempotency,
+        }
+    }
+
+    /// Returns the policy every transition of this manager is validated through.
+    pub fn policy(&self) -> &TransitionPolicy<S> {
+        &self.policy
+    }
+
+    /// Moves a booking the caller holds to the `to` status, validated through this manager's policy
+    /// and rules, and records the change described by `context` in its history.
+    ///
+    /// Returns `BookingError::InvalidStatusTransition` if the policy does not allow it, and
+    /// `BookingError::RuleValidationFailed` if a rule rejects it. Rules see no other bookings here;
+    /// use `transition_booking` to evaluate them against the repository.
+    pub fn transition<T, ID, Timestamp, Metadata>(
+        &self,
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+        to: S,
+        context: ChangeContext<ID, Timestamp>,
+    ) -> Result<(), BookingError>
+    where
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable,
+        ID: Clone,
+        Timestamp: Clone,
+    {
+        self.apply_transition(booking, to, context, &[], &[])?;
+        self.publish_last_change(booking);
+        Ok(())
+    }
+
+    /// Validates a transition through the policy and the rules, then applies it.
+    fn apply_transition<T, ID, Timestamp, Metadata>(
+        &self,
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+        to: S,
+        context: ChangeContext<ID, Timestamp>,
+        user_bookings: &[Booking<T, ID, Timestamp, Metadata, S>],
+        item_bookings: &[Booking<T, ID, Timestamp, Metadata, S>],
+    ) -> Result<(), BookingError>
+    where
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        S: Status,
+        T: Bookable,
+    {
+        self.policy.validate(&booking.status, &to)?;
+
+        let transition = Transition {
+            from: booking.status.clone(),
+            to: to.clone(),
+        };
+        let rule_context = RuleContext::transition(transition, context.at.as_ref())
+            .with_user_bookings(user_bookings)
+            .with_item_bookings(item_bookings);
+        self.rules.evaluate(booking, &rule_context)?;
+
+        BookingManager::transition_with(&self.policy, booking, to, context)
+    }
+
+    /// Returns a reference to the underlying repository.
+    pub fn repository(&self) -> &R {
+        &self.repository
+    }
+
+    /// Consumes the manager and returns the underlying repository.
+    pub fn into_repository(self) -> R {
+        self.repository
+    }
+
+    /// Creates a new booking with status `Pending` and stores it, without checking item availability.
+    ///
+    /// Returns `BookingError::DuplicateBooking` if a booking with the same ID is already stored.
+    pub fn create_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: ID,
+        user_id: Option<ID>,
+        item: T,
+        created_at: Timestamp,
+        expires_at: Option<Timestamp>,
+        metadata: Option<Metadata>,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: Clone,
+        Timestamp: Clone,
+        Metadata: Clone,
+    {
+        let status = S::try_from_booking_status(&BookingStatus::Pending)?;
+        let booking = BookingManager::create_with_status(booking_id, user_id, item, status, created_at, expires_at, metadata);
+        self.repository.insert(booking.clone())?;
+        self.subscriber.notify(&BookingEvent::created(&booking));
+        Ok(booking)
+    }
+
+    /// Attempts to create a booking after checking item availability and conflicts, and stores it.
+    ///
+    /// Returns `BookingError::ItemUnavailable` if the item is not available.
+    /// See `try_insert` for the checks performed against stored bookings.
+    pub fn try_create_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: ID,
+        user_id: Option<ID>,
+        item: T,
+        created_at: Timestamp,
+        expires_at: Option<Timestamp>,
+        metadata: Option<Metadata>,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        let status = S::try_from_booking_status(&BookingStatus::Pending)?;
+        let booking = BookingManager::create_with_status(booking_id, user_id, item, status, created_at, expires_at, metadata);
+        self.try_insert(booking)
+    }
+
+    /// Validates a booking built by the caller (e.g. with `BookingManager::create(..).with_period(..)`)
+    /// against the repository, and stores it.
+    ///
+    /// Returns `BookingError::ItemUnavailable` if the item is not available. For items with a
+    /// `Bookable::capacity()`, returns `BookingError::QuantityExceeded` when the active bookings
+    /// sharing that capacity leave too few units; otherwise returns `BookingError::BookingConflict`
+    /// if an active booking of the same item overlaps its period. Finally, the manager's rules are
+    /// evaluated with the booking's `created_at` as the current time, and
+    /// `BookingError::RuleValidationFailed` is returned if one rejects the booking.
+    ///
+    /// The checks run through `BookingRepository::insert_checked`, so concurrent inserts cannot
+    /// both pass them against the same stored bookings.
+    pub fn try_insert<T, ID, Timestamp, Metadata>(
+        &self,
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        if !booking.item.is_available() {
+            return Err(BookingError::new_item_unavailable(booking.item_id()));
+        }
+
+        self.repository
+            .insert_checked(vec![booking.clone()], &mut |stored| self.check_against(&booking, stored))?;
+        self.subscriber.notify(&BookingEvent::created(&booking));
+        Ok(booking)
+    }
+
+    /// Stores a booking like `try_insert`, deduplicating retries of the same request by `key`.
+    ///
+    /// The first call with a given key claims it in the manager's `IdempotencyStore` and records
+    /// its outcome. Later calls with the same key and an equal booking return that outcome again:
+    /// the stored booking as it currently is, or a clone of the original error. No event is
+    /// emitted on replay. Storage errors and concurrent modifications are not recorded, so
+    /// retrying after one runs the request again; neither is a panic while storing, which
+    /// releases the key as it unwinds.
+    ///
+    /// Returns `BookingError::IdempotencyKeyReused` if the key was first used with a different
+    /// booking, `BookingError::IdempotencyKeyInUse` while the first request is still running,
+    /// `BookingError::BookingNotFound` on replay if the booking has since been removed, and
+    /// `BookingError::CreationFailed` if the manager has no idempotency store.
+    pub fn try_insert_idempotent<T, ID, Timestamp, Metadata>(
+        &self,
+        key: &str,
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        K: IdempotencyStore<ID, Booking<T, ID, Timestamp, Metadata, S>>,
+        S: Status,
+        T: Bookable + Clone + PartialEq,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone + PartialEq,
+    {
+        if let Some(record) = self.idempotency.claim(key, &booking)? {
+            if record.payload != booking {
+                return Err(BookingError::new_idempotency_key_reused(key));
+            }
+            let booking_id = record.outcome.ok_or_else(|| BookingError::new_idempotency_key_in_use(key))??;
+            return self
+                .repository
+                .get(&booking_id)?
+                .ok_or_else(|| BookingError::new_booking_not_found(&booking_id.to_string()));
+        }
+
+        let guard = ClaimGuard::new(&self.idempotency, key);
+        let result = self.try_insert(booking);
+        guard.disarm();
+        match &result {
+            Ok(stored) => self.idempotency.complete(key, Ok(stored.id.clone()))?,
+            Err(BookingError::StorageError(_) | BookingError::ConcurrentModification { .. }) => {
+                self.idempotency.release(key)?
+            }
+            Err(e) => self.idempotency.complete(key, Err(e.clone()))?,
+        }
+        result
+    }
+
+    /// Checks capacity or conflicts and the rules for `booking` against `known` bookings: those
+    /// passed to the check of `BookingRepository::insert_checked`, plus any stored alongside it.
+    fn check_against<T, ID, Timestamp, Metadata>(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        known: &[Booking<T, ID, Timestamp, Metadata, S>],
+    ) -> Result<(), BookingError>
+    where
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        let (item_bookings, user_bookings) = related_bookings(booking, known);
+        check_fits(booking, &item_bookings)?;
+        self.check_creation_rules(booking, &item_bookings, &user_bookings)
+    }
+
+    /// Evaluates the rules for a new `booking` with its `created_at` as the current time.
+    fn check_creation_rules<T, ID, Timestamp, Metadata>(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        item_bookings: &[Booking<T, ID, Timestamp, Metadata, S>],
+        user_bookings: &[Booking<T, ID, Timestamp, Metadata, S>],
+    ) -> Result<(), BookingError>
+    where
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        S: Status,
+    {
+        let rule_context = RuleContext::creation(&booking.created_at)
+            .with_user_bookings(user_bookings)
+            .with_item_bookings(item_bookings);
+        self.rules.evaluate(booking, &rule_context)?;
+        Ok(())
+    }
+
+    /// Creates a multi-item booking and stores all of its lines, or none of them.
+    ///
+    /// Every line is checked like `try_insert`, both against the repository and against the
+    /// other lines of the same booking, and all lines are stored with a single
+    /// `BookingRepository::insert_checked`. Returns `BookingError::ItemUnavailable` listing every
+    /// unavailable item ID, or the first other error found.
+    pub fn try_create_multi_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: ID,
+        user_id: Option<ID>,
+        lines: Vec<Booking<T, ID, Timestamp, Metadata, S>>,
+    ) -> Result<MultiBooking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        let multi = create_multi(booking_id, user_id, lines)?;
+        self.repository.insert_checked(multi.lines.clone(), &mut |stored| {
+            let mut known = stored.to_vec();
+            for line in &multi.lines {
+                self.check_against(line, &known)?;
+                known.push(line.clone());
+            }
+            Ok(())
+        })?;
+
+        for line in &multi.lines {
+            self.subscriber.notify(&BookingEvent::created(line));
+        }
+        Ok(multi)
+    }
+
+    /// Runs every check of `try_insert` against the repository without stopping at the first
+    /// failure, and returns all of them in a `ValidationReport`. Nothing is stored.
+    ///
+    /// Rule failures are reported individually (every failing rule of an `All` is listed).
+    /// Repository errors are reported as `storage_error` issues.
+    pub fn validate<T, ID, Timestamp, Metadata>(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> ValidationReport
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        S: Status,
+        T: Bookable,
+        ID: PartialEq + Display,
+        Timestamp: PartialOrd,
+    {
+        let mut report = ValidationReport::new();
+
+        if !booking.item.is_available() {
+            report.push(BookingError::new_item_unavailable(booking.item_id()));
+        }
+
+        let existing = self.repository.list_by_item(booking.item_id()).unwrap_or_else(|e| {
+            report.push(e);
+            Vec::new()
+        });
+        let user_bookings = match &booking.user_id {
+            Some(user_id) => self.repository.list_by_user(user_id).unwrap_or_else(|e| {
+                report.push(e);
+                Vec::new()
+            }),
+            None => Vec::new(),
+        };
+        self.report_against(booking, &existing, &user_bookings, &mut report);
+        report
+    }
+
+    /// Adds the capacity or conflict failure and every rule failure of `booking` to `report`.
+    fn report_against<T, ID, Timestamp, Metadata>(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        item_bookings: &[Booking<T, ID, Timestamp, Metadata, S>],
+        user_bookings: &[Booking<T, ID, Timestamp, Metadata, S>],
+        report: &mut ValidationReport,
+    ) where
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        S: Status,
+        T: Bookable,
+        ID: PartialEq + Display,
+        Timestamp: PartialOrd,
+    {
+        if let Err(e) = check_fits(booking, item_bookings) {
+            report.push(e);
+        }
+
+        let rule_context = RuleContext::creation(&booking.created_at)
+            .with_user_bookings(user_bookings)
+            .with_item_bookings(item_bookings);
+        for failure in self.rules.evaluate_all(booking, &rule_context) {
+            report.push(failure);
+        }
+    }
+
+    /// Like `try_insert`, but reports every reason the booking was rejected instead of the first one.
+    ///
+    /// As with `try_insert`, the checks and the insert happen in one
+    /// `BookingRepository::insert_checked`.
+    pub fn try_insert_reporting<T, ID, Timestamp, Metadata>(
+        &self,
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, ValidationReport>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        let mut report = ValidationReport::new();
+        if !booking.item.is_available() {
+            report.push(BookingError::new_item_unavailable(booking.item_id()));
+        }
+
+        let stored = self.repository.insert_checked(vec![booking.clone()], &mut |stored| {
+            let (item_bookings, user_bookings) = related_bookings(&booking, stored);
+            self.report_against(&booking, &item_bookings, &user_bookings, &mut report);
+            match report.is_valid() {
+                true => Ok(()),
+                false => Err(BookingError::new_creation_failed("booking failed validation")),
+            }
+        });
+        if let Err(e) = stored {
+            if report.is_valid() {
+                report.push(e);
+            }
+            return Err(report);
+        }
+        self.subscriber.notify(&BookingEvent::created(&booking));
+        Ok(booking)
+    }
+
+    /// Loads the booking with the given ID, marks it as `Confirmed`, and stores the result.
+    ///
+    /// Returns `BookingError::BookingNotFound` if the booking does not exist, and
+    /// `BookingError::InvalidStatusTransition` if this manager's policy does not allow the move.
+    pub fn confirm_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: &ID,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        self.transition_booking(booking_id, S::try_from_booking_status(&BookingStatus::Confirmed)?, ChangeContext::new())
+    }
+
+    /// Loads the booking with the given ID, marks it as `Canceled`, and stores the result.
+    ///
+    /// Returns `BookingError::BookingNotFound` if the booking does not exist, and
+    /// `BookingError::InvalidStatusTransition` if this manager's policy does not allow the move.
+    pub fn cancel_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: &ID,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        self.transition_booking(booking_id, S::try_from_booking_status(&BookingStatus::Canceled)?, ChangeContext::new())
+    }
+
+    /// Loads the booking with the given ID, marks it as `Expired`, and stores the result.
+    ///
+    /// Returns `BookingError::BookingNotFound` if the booking does not exist, and
+    /// `BookingError::InvalidStatusTransition` if this manager's policy does not allow the move.
+    pub fn expire_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: &ID,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        self.transition_booking(booking_id, S::try_from_booking_status(&BookingStatus::Expired)?, ChangeContext::new())
+    }
+
+    /// Loads the booking with the given ID, marks it as `Completed`, and stores the result.
+    ///
+    /// Returns `BookingError::BookingNotFound` if the booking does not exist, and
+    /// `BookingError::InvalidStatusTransition` if this manager's policy does not allow the move.
+    pub fn complete_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: &ID,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        self.transition_booking(booking_id, S::try_from_booking_status(&BookingStatus::Completed)?, ChangeContext::new())
+    }
+
+    /// Loads the booking with the given ID, marks it as `Failed`, and stores the result.
+    ///
+    /// Returns `BookingError::BookingNotFound` if the booking does not exist, and
+    /// `BookingError::InvalidStatusTransition` if this manager's policy does not allow the move.
+    pub fn fail_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: &ID,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        self.transition_booking(booking_id, S::try_from_booking_status(&BookingStatus::Failed)?, ChangeContext::new())
+    }
+
+    /// Loads the booking with the given ID, marks it as `CheckedIn`, and stores the result.
+    ///
+    /// Returns `BookingError::BookingNotFound` if the booking does not exist, and
+    /// `BookingError::InvalidStatusTransition` if this manager's policy does not allow the move.
+    pub fn check_in_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: &ID,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        self.transition_booking(booking_id, S::try_from_booking_status(&BookingStatus::CheckedIn)?, ChangeContext::new())
+    }
+
+    /// Loads the booking with the given ID, marks it as `NoShow`, and stores the result.
+    ///
+    /// Returns `BookingError::BookingNotFound` if the booking does not exist, and
+    /// `BookingError::InvalidStatusTransition` if this manager's policy does not allow the move.
+    pub fn mark_no_show_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: &ID,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        self.transition_booking(booking_id, S::try_from_booking_status(&BookingStatus::NoShow)?, ChangeContext::new())
+    }
+
+    /// Loads the booking with the given ID, marks it as `Refunded`, and stores the result.
+    ///
+    /// Returns `BookingError::BookingNotFound` if the booking does not exist, and
+    /// `BookingError::InvalidStatusTransition` if this manager's policy does not allow the move.
+    pub fn refund_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: &ID,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        self.transition_booking(booking_id, S::try_from_booking_status(&BookingStatus::Refunded)?, ChangeContext::new())
+    }
+
+    /// Loads the booking with the given ID, moves it to the `to` status, records the change
+    /// described by `context` in its history, and stores the result.
+    ///
+    /// Use this over the shorthand operations to record who made the change and why.
+    /// Returns `BookingError::BookingNotFound` if the booking does not exist,
+    /// `BookingError::InvalidStatusTransition` if this manager's policy does not allow the move, and
+    /// `BookingError::RuleValidationFailed` if one of its rules rejects it.
+    pub fn transition_booking<T, ID, Timestamp, Metadata>(
+        &self,
+        booking_id: &ID,
+        to: S,
+        context: ChangeContext<ID, Timestamp>,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        let booking = self.modify(booking_id, |booking| self.apply_stored_transition(booking, to, context))?;
+        self.publish_last_change(&booking);
+
+        // The transition is already stored, so waitlist failures do not fail it: stale entries
+        // are skipped by the next promotion, which can also be run with `promote_waitlisted`.
+        if let Some(change) = booking.last_change() {
+            let waitlisted = S::from_booking_status(&BookingStatus::Waitlisted);
+            if waitlisted.is_some_and(|waitlisted| change.from == waitlisted && change.to != waitlisted) {
+                let _ = self.waitlist.remove(&booking.id);
+            }
+            if change.from.is_active() && !change.to.is_active() {
+                let _ = self.promote_waitlisted(booking.item_id());
+            }
+        }
+        Ok(booking)
+    }
+
+    /// Places a temporary hold on the item of `booking` for `ttl` after its `created_at`, e.g. while
+    /// payment runs, and stores it.
+    ///
+    /// The hold is stored in `OnHold` status with `expires_at` set to the end of the hold, after the
+    /// same checks as `try_insert`. It counts against availability until it is converted with
+    /// `convert_hold`, released with `cancel_booking`, or its `expires_at` passes; overdue holds are
+    /// moved to `Expired` by `ExpirySweeper`. Returns `BookingError::CreationFailed` if the end of
+    /// the hold cannot be represented.
+    pub fn try_hold<T, ID, Timestamp, Metadata>(
+        &self,
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+        ttl: &Timestamp::Duration,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: BookingTime,
+        Metadata: Clone,
+    {
+        let mut booking = booking;
+        let expires_at = booking
+            .created_at
+            .checked_plus(ttl)
+            .ok_or_else(|| BookingError::new_creation_failed("hold expiry is out of range"))?;
+        booking.status = S::try_from_booking_status(&BookingStatus::OnHold)?;
+        booking.expires_at = Some(expires_at);
+        self.try_insert(booking)
+    }
+
+    /// Converts a stored hold into a `Pending` or `Confirmed` booking at `now`, and stores it.
+    ///
+    /// The hold's `expires_at` is cleared, as it no longer applies to the booking. Returns
+    /// `BookingError::BookingNotFound` if the hold does not exist,
+    /// `BookingError::InvalidStatusTransition` if the booking is not on hold or `to` is neither
+    /// `Pending` nor `Confirmed`, and `BookingError::HoldExpired` if its `expires_at` has passed,
+    /// in which case the hold is moved to `Expired`.
+    pub fn convert_hold<T, ID, Timestamp, Metadata>(
+        &self,
+        hold_id: &ID,
+        to: S,
+        now: Timestamp,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        let converts_to = |status| S::from_booking_status(&status).is_some_and(|status| status == to);
+        if !converts_to(BookingStatus::Pending) && !converts_to(BookingStatus::Confirmed) {
+            return Err(S::try_from_booking_status(&BookingStatus::OnHold)?.transition_error(&to));
+        }
+
+        let hold = self
+            .repository
+            .get(hold_id)?
+            .ok_or_else(|| BookingError::new_booking_not_found(&hold_id.to_string()))?;
+        if !hold.is_on_hold() {
+            return Err(hold.status.transition_error(&to));
+        }
+        if hold.is_past_expiry(&now) {
+            let context = ChangeContext::new().at(now).because(EXPIRY_REASON);
+            self.transition_booking(hold_id, S::try_from_booking_status(&BookingStatus::Expired)?, context)?;
+            return Err(BookingError::new_hold_expired(&hold_id.to_string()));
+        }
+
+        let booking = self.modify(hold_id, |booking| {
+            self.apply_stored_transition(booking, to, ChangeContext::new().at(now))?;
+            booking.expires_at = None;
+            Ok(())
+        })?;
+        self.publish_last_change(&booking);
+        Ok(booking)
+    }
+
+    /// Attempts to create a booking like `try_insert`, and puts it on the waitlist instead if its
+    /// item is taken.
+    ///
+    /// When `try_insert` fails with `BookingError::ItemUnavailable`, `BookingError::QuantityExceeded`
+    /// or `BookingError::BookingConflict`, the manager's rules are still evaluated, then the booking
+    /// is stored in `Waitlisted` status and queued with the given `priority`. Check the returned
+    /// booking with `is_waitlisted`. Any other error is returned unchanged.
+    pub fn try_create_or_waitlist<T, ID, Timestamp, Metadata>(
+        &self,
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+        priority: i32,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        match self.try_insert(booking.clone()) {
+            Err(BookingError::ItemUnavailable(_))
+            | Err(BookingError::QuantityExceeded)
+            | Err(BookingError::BookingConflict { .. }) => {}
+            result => return result,
+        }
+
+        let mut booking = booking;
+        booking.status = S::try_from_booking_status(&BookingStatus::Waitlisted)?;
+        self.repository.insert_checked(vec![booking.clone()], &mut |stored| {
+            let (item_bookings, user_bookings) = related_bookings(&booking, stored);
+            self.check_creation_rules(&booking, &item_bookings, &user_bookings)
+        })?;
+        if let Err(e) = self.waitlist.enqueue(booking.item_id(), booking.id.clone(), priority) {
+            let _ = self.repository.remove(&booking.id);
+            return Err(e);
+        }
+        self.subscriber.notify(&BookingEvent::created(&booking));
+        Ok(booking)
+    }
+
+    /// Promotes waitlisted bookings of the given item to `Pending`, in waitlist order, as long as
+    /// they fit the capacity or period left by the active bookings.
+    ///
+    /// Called automatically when an active booking is canceled, expires, fails or completes through
+    /// this manager. Every entry that fits is promoted, not only the first: a freed booking can
+    /// leave room for several smaller ones (seats of a flight) or for entries asking for other
+    /// periods. Each promotion is checked and stored as one step with
+    /// `BookingRepository::update_checked`, against the bookings stored at that moment including
+    /// those promoted before it, so concurrent promotions and inserts cannot overbook the item.
+    ///
+    /// Entries that do not fit, that a rule rejects, or that changed concurrently keep waiting;
+    /// entries whose booking is gone or no longer waitlisted are dropped. Item availability is not
+    /// re-checked, as the stored item is a snapshot taken when the booking was made.
+    /// Returns the promoted bookings.
+    pub fn promote_waitlisted<T, ID, Timestamp, Metadata>(
+        &self,
+        item_id: &str,
+    ) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        let mut promoted = Vec::new();
+        for booking_id in self.waitlist.candidates(item_id)? {
+            let booking = match self.repository.get(&booking_id)? {
+                Some(booking) if booking.is_waitlisted() => booking,
+                _ => {
+                    self.waitlist.remove(&booking_id)?;
+                    continue;
+                }
+            };
+
+            let pending = S::try_from_booking_status(&BookingStatus::Pending)?;
+            let context = ChangeContext::new().because(PROMOTION_REASON);
+            let mut next = booking.clone();
+            let result = BookingManager::transition_with(&self.policy, &mut next, pending.clone(), context.clone())
+                .and_then(|()| {
+                    self.repository.update_checked(next.clone(), &mut |stored| {
+                        let (item_bookings, user_bookings) = related_bookings(&booking, stored);
+                        check_fits(&booking, &item_bookings)?;
+                        self.apply_transition(&mut booking.clone(), pending.clone(), context.clone(), &user_bookings, &item_bookings)
+                    })
+                });
+            match result {
+                Ok(()) => {
+                    let _ = self.waitlist.remove(&booking_id);
+                    self.publish_last_change(&next);
+                    promoted.push(next);
+                }
+                Err(BookingError::RuleValidationFailed(_))
+                | Err(BookingError::InvalidStatus(_))
+                | Err(BookingError::InvalidStatusTransition { .. })
+                | Err(BookingError::QuantityExceeded)
+                | Err(BookingError::BookingConflict { .. })
+                | Err(BookingError::ConcurrentModification { .. }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(promoted)
+    }
+
+    /// Moves one stored line of a multi-item booking to the `to` status like `transition_booking`,
+    /// and refreshes that line in `multi` with the stored result.
+    ///
+    /// Returns `BookingError::BookingNotFound` if `multi` has no line with the given ID or the line
+    /// is not stored, and otherwise the same errors as `transition_booking`.
+    pub fn transition_booking_line<T, ID, Timestamp, Metadata>(
+        &self,
+        multi: &mut MultiBooking<T, ID, Timestamp, Metadata, S>,
+        line_id: &ID,
+        to: S,
+        context: ChangeContext<ID, Timestamp>,
+    ) -> Result<(), BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        let line = multi
+            .line_mut(line_id)
+            .ok_or_else(|| BookingError::new_booking_not_found(&line_id.to_string()))?;
+        *line = self.transition_booking(line_id, to, context)?;
+        Ok(())
+    }
+
+    /// Marks one stored line of a multi-item booking as `Confirmed`.
+    pub fn confirm_booking_line<T, ID, Timestamp, Metadata>(
+        &self,
+        multi: &mut MultiBooking<T, ID, Timestamp, Metadata, S>,
+        line_id: &ID,
+    ) -> Result<(), BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        self.transition_booking_line(multi, line_id, S::try_from_booking_status(&BookingStatus::Confirmed)?, ChangeContext::new())
+    }
+
+    /// Marks one stored line of a multi-item booking as `Canceled`, releasing its item while the
+    /// other lines stay booked.
+    pub fn cancel_booking_line<T, ID, Timestamp, Metadata>(
+        &self,
+        multi: &mut MultiBooking<T, ID, Timestamp, Metadata, S>,
+        line_id: &ID,
+    ) -> Result<(), BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        self.transition_booking_line(multi, line_id, S::try_from_booking_status(&BookingStatus::Canceled)?, ChangeContext::new())
+    }
+
+    /// Validates and applies a transition to a stored booking, evaluating the rules against the
+    /// stored bookings of its user and item.
+    fn apply_stored_transition<T, ID, Timestamp, Metadata>(
+        &self,
+        booking: &mut Booking<T, ID, Timestamp, Metadata, S>,
+        to: S,
+        context: ChangeContext<ID, Timestamp>,
+    ) -> Result<(), BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        S: Status,
+        T: Bookable,
+    {
+        let user_bookings = match &booking.user_id {
+            Some(user_id) => self.repository.list_by_user(user_id)?,
+            None => Vec::new(),
+        };
+        let item_bookings = self.repository.list_by_item(booking.item_id())?;
+        self.apply_transition(booking, to, context, &user_bookings, &item_bookings)
+    }
+
+    /// Notifies the subscriber of the most recent status change of `booking`.
+    fn publish_last_change<T, ID, Timestamp, Metadata>(&self, booking: &Booking<T, ID, Timestamp, Metadata, S>)
+    where
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable,
+        ID: Clone,
+        Timestamp: Clone,
+    {
+        if let Some(change) = booking.last_change() {
+            self.subscriber.notify(&BookingEvent::transitioned(booking, change));
+        }
+    }
+
+    /// Loads a booking, applies `change` to it, and persists the result if the change succeeded.
+    ///
+    /// `change` is expected to transition the booking, which increments its `version`.
+    ///
+    /// Returns `BookingError::ConcurrentModification` if the booking was updated by someone else
+    /// in the meantime.
+    fn modify<T, ID, Timestamp, Metadata, F>(
+        &self,
+        booking_id: &ID,
+        change: F,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: Display + Clone,
+        Timestamp: Clone,
+        Metadata: Clone,
+        F: FnOnce(&mut Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError>,
+    {
+        let mut booking = self
+            .repository
+            .get(booking_id)?
+            .ok_or_else(|| BookingError::new_booking_not_found(&booking_id.to_string()))?;
+
+        change(&mut booking)?;
+        self.repository.update(booking.clone())?;
+        Ok(booking)
     }
 }
@@ -1,7 +1,12 @@
 //! Defines core entities like `Booking`, and related data structures.
 
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-use crate::model::status::BookingStatus;
+use crate::model::history::StatusChange;
+use crate::model::period::BookingPeriod;
+use crate::model::status::{BookingStatus, Status};
+use crate::timestamp::BookingTime;
 use crate::traits::Bookable;
 
 /// Represents a generic booking entry.
@@ -14,33 +19,57 @@ use crate::traits::Bookable;
 /// - `ID`: Identifier type used for both booking ID and user ID. Typically `String` or `&str`.
 /// - `Timestamp`: Type representing time values. This is left generic to allow use of custom types like `String`, `i64`, or `chrono::DateTime`.
 /// - `Metadata`: Optional additional data specific to the domain, such as payment info or notes.
+/// - `S`: The set of statuses the booking goes through, implementing `Status`. Defaults to `BookingStatus`.
 ///
 /// # Fields
 /// - `id`: Unique identifier for this booking.
 /// - `user_id`: Optional ID of the user making the booking.
 /// - `item`: The item that is being booked.
+/// - `quantity`: Number of units of the item reserved by this booking. Defaults to `1`.
 /// - `status`: Current status of the booking (e.g., `Pending`, `Confirmed`, `Canceled`).
 /// - `created_at`: Timestamp indicating when the booking was created.
 /// - `expires_at`: Optional timestamp indicating when the booking expires (if applicable).
+/// - `period`: Optional period the item is reserved for; used to detect overlapping bookings.
 /// - `metadata`: Optional metadata for extending the booking with domain-specific information.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct Booking<T, ID, Timestamp, Metadata> {
+/// - `history`: Status changes made through `BookingManager`, oldest first. Omitted from serialized output when empty.
+/// - `version`: Number of status transitions applied to the booking, starting at `0`. Repositories
+///   reject updates based on an outdated version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Booking<T, ID, Timestamp, Metadata, S = BookingStatus> {
     pub id: ID,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub user_id: Option<ID>,
     pub item: T,
-    pub status: BookingStatus,
+
+    #[cfg_attr(feature = "serde", serde(default = "default_quantity"))]
+    pub quantity: u32,
+    pub status: S,
     pub created_at: Timestamp,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub expires_at: Option<Timestamp>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub period: Option<BookingPeriod<Timestamp>>,
+
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub metadata: Option<Metadata>,
+
+    #[cfg_attr(feature = "serde", serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty"))]
+    pub history: Vec<StatusChange<ID, Timestamp, S>>,
+
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub version: u64,
+}
+
+#[cfg(feature = "serde")]
+fn default_quantity() -> u32 {
+    1
 }
 
-impl<T, ID, Timestamp, Metadata> Booking<T, ID, Timestamp, Metadata>
+impl<T, ID, Timestamp, Metadata, S> Booking<T, ID, Timestamp, Metadata, S>
 where
     T: Bookable,
 {
@@ -52,55 +81,208 @@ where
         self.item.id()
     }
 
+    /// Sets the period the item is reserved for, from `start` (inclusive) to `end` (exclusive).
+    ///
+    /// Intended to be chained after `BookingManager::create`.
+    pub fn with_period(mut self, start: Timestamp, end: Timestamp) -> Self {
+        self.period = Some(BookingPeriod::new(start, end));
+        self
+    }
+
+    /// Sets the number of units of the item this booking reserves.
+    ///
+    /// Intended to be chained after `BookingManager::create`.
+    pub fn with_quantity(mut self, quantity: u32) -> Self {
+        self.quantity = quantity;
+        self
+    }
+
+    /// Sets `expires_at` to `ttl` after `created_at`, e.g. for a payment hold.
+    ///
+    /// Leaves `expires_at` unchanged if the addition overflows.
+    pub fn with_ttl(mut self, ttl: &Timestamp::Duration) -> Self
+    where
+        Timestamp: BookingTime,
+    {
+        if let Some(expires_at) = self.created_at.checked_plus(ttl) {
+            self.expires_at = Some(expires_at);
+        }
+        self
+    }
+
     /// Checks if the booking is in a `Pending` state.
     ///
     /// This is useful to determine if a booking is awaiting confirmation.
-    pub fn is_pending(&self) -> bool {
-        self.status == BookingStatus::Pending
+    pub fn is_pending(&self) -> bool
+    where
+        S: Status,
+    {
+        self.has_status(BookingStatus::Pending)
     }
 
     /// Checks if the booking has been confirmed.
     ///
     /// Use this to verify that the booking was successfully completed.
-    pub fn is_confirmed(&self) -> bool {
-        self.status == BookingStatus::Confirmed
+    pub fn is_confirmed(&self) -> bool
+    where
+        S: Status,
+    {
+        self.has_status(BookingStatus::Confirmed)
     }
 
     /// Checks if the booking was canceled.
-    pub fn is_canceled(&self) -> bool {
-        self.status == BookingStatus::Canceled
+    pub fn is_canceled(&self) -> bool
+    where
+        S: Status,
+    {
+        self.has_status(BookingStatus::Canceled)
+    }
+
+    /// Checks if the booking is waiting on the waitlist for its item to free up.
+    pub fn is_waitlisted(&self) -> bool
+    where
+        S: Status,
+    {
+        self.has_status(BookingStatus::Waitlisted)
+    }
+
+    /// Checks if the booking is a temporary hold on its item.
+    pub fn is_on_hold(&self) -> bool
+    where
+        S: Status,
+    {
+        self.has_status(BookingStatus::OnHold)
     }
 
     /// Checks if the booking has expired.
     ///
     /// This does not automatically evaluate time; it simply checks the status field.
-    pub fn is_expired(&self) -> bool {
-        self.status == BookingStatus::Expired
+    pub fn is_expired(&self) -> bool
+    where
+        S: Status,
+    {
+        self.has_status(BookingStatus::Expired)
+    }
+
+    /// Checks if the guest has arrived and is using the booked item.
+    pub fn is_checked_in(&self) -> bool
+    where
+        S: Status,
+    {
+        self.has_status(BookingStatus::CheckedIn)
+    }
+
+    /// Checks if the guest never arrived for the booking.
+    pub fn is_no_show(&self) -> bool
+    where
+        S: Status,
+    {
+        self.has_status(BookingStatus::NoShow)
+    }
+
+    /// Checks if the booking was called off and its payment returned.
+    pub fn is_refunded(&self) -> bool
+    where
+        S: Status,
+    {
+        self.has_status(BookingStatus::Refunded)
+    }
+
+    /// Returns `true` if `expires_at` is set and is not after `now`.
+    ///
+    /// Unlike `is_expired`, this evaluates the timestamp rather than the status field.
+    pub fn is_past_expiry(&self, now: &Timestamp) -> bool
+    where
+        Timestamp: PartialOrd,
+    {
+        self.expires_at.as_ref().is_some_and(|expires_at| expires_at <= now)
     }
 
     /// Returns `true` if the booking is considered active.
     ///
-    /// Typically, this includes `Pending` and `Confirmed` states.
-    pub fn is_active(&self) -> bool {
-        matches!(self.status, BookingStatus::Pending | BookingStatus::Confirmed)
+    /// Typically, this includes `Pending`, `Confirmed` and `CheckedIn` states.
+    pub fn is_active(&self) -> bool
+    where
+        S: Status,
+    {
+        self.status.is_active()
+    }
+
+    /// Returns `true` if the booking holds its item at `now`.
+    ///
+    /// Like `is_active`, except that a hold whose `expires_at` has passed no longer holds its item,
+    /// even before it is moved to `Expired`.
+    pub fn holds_item_at(&self, now: &Timestamp) -> bool
+    where
+        S: Status,
+        Timestamp: PartialOrd,
+    {
+        self.is_active() && !(self.is_on_hold() && self.is_past_expiry(now))
     }
 
     /// Returns `true` if the booking is in a final state and can no longer be modified.
     ///
-    /// Final states usually include `Canceled` and `Expired`.
-    pub fn is_final(&self) -> bool {
-        matches!(
-            self.status,
-            BookingStatus::Canceled |
-            BookingStatus::Expired |
-            BookingStatus::Failed |
-            BookingStatus::Completed
-        )
+    /// Final states usually include `Canceled`, `Expired` and `NoShow`.
+    pub fn is_final(&self) -> bool
+    where
+        S: Status,
+    {
+        self.status.is_final()
+    }
+
+    /// Returns `true` if the booking is in the status playing the role of the built-in `status`,
+    /// as given by `Status::from_booking_status`.
+    pub fn has_status(&self, status: BookingStatus) -> bool
+    where
+        S: Status,
+    {
+        S::from_booking_status(&status).is_some_and(|status| status == self.status)
+    }
+
+    /// Returns the most recent status change, if the booking has been transitioned.
+    pub fn last_change(&self) -> Option<&StatusChange<ID, Timestamp, S>> {
+        self.history.last()
     }
 
     /// Returns true if the booking is completed successfully.
-    pub fn is_completed(&self) -> bool {
-        self.status == BookingStatus::Completed
+    pub fn is_completed(&self) -> bool
+    where
+        S: Status,
+    {
+        self.has_status(BookingStatus::Completed)
+    }
+
+    /// Returns `true` if this booking and `other` reserve the same item for overlapping periods.
+    ///
+    /// Bookings without a period never overlap. See `shares_capacity_with` for capacity-managed items.
+    pub fn overlaps<U, OtherID, OtherMetadata, OtherS>(&self, other: &Booking<U, OtherID, Timestamp, OtherMetadata, OtherS>) -> bool
+    where
+        U: Bookable,
+        Timestamp: PartialOrd,
+    {
+        match (&self.period, &other.period) {
+            (Some(this), Some(that)) => self.item_id() == other.item_id() && this.overlaps(that),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if this booking and `other` draw from the same capacity of the same item.
+    ///
+    /// Unlike `overlaps`, a booking without a period holds its units for the item's whole lifetime,
+    /// so it competes with every other booking of the same item.
+    pub fn shares_capacity_with<U, OtherID, OtherMetadata, OtherS>(&self, other: &Booking<U, OtherID, Timestamp, OtherMetadata, OtherS>) -> bool
+    where
+        U: Bookable,
+        Timestamp: PartialOrd,
+    {
+        if self.item_id() != other.item_id() {
+            return false;
+        }
+
+        match (&self.period, &other.period) {
+            (Some(this), Some(that)) => this.overlaps(that),
+            _ => true,
+        }
     }
 
 }
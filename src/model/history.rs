@@ -0,0 +1,98 @@
+//! Defines the status history recorded on a booking for every transition.
+
+use alloc::string::String;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::model::status::BookingStatus;
+
+/// A single entry in a booking's status history.
+///
+/// # Fields
+/// - `from`: Status the booking had before the change.
+/// - `to`: Status the booking moved to.
+/// - `at`: Optional timestamp of the change.
+/// - `actor`: Optional ID of the user or system that made the change.
+/// - `reason`: Optional free-form explanation, e.g. `"customer request"` or `"payment declined"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StatusChange<ID, Timestamp, S = BookingStatus> {
+    pub from: S,
+    pub to: S,
+
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub at: Option<Timestamp>,
+
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub actor: Option<ID>,
+
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub reason: Option<String>,
+}
+
+/// Describes who made a status change, when, and why.
+///
+/// Passed to the `BookingManager` transition functions and copied into the recorded `StatusChange`.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::model::history::ChangeContext;
+///
+/// let context: ChangeContext<&str, i64> = ChangeContext::new()
+///     .at(1_700_000_000)
+///     .by("agent-42")
+///     .because("customer called to cancel");
+///
+/// assert_eq!(context.actor, Some("agent-42"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeContext<ID, Timestamp> {
+    pub at: Option<Timestamp>,
+    pub actor: Option<ID>,
+    pub reason: Option<String>,
+}
+
+impl<ID, Timestamp> ChangeContext<ID, Timestamp> {
+    /// Creates an empty context: no timestamp, actor, or reason.
+    pub fn new() -> Self {
+        Self {
+            at: None,
+            actor: None,
+            reason: None,
+        }
+    }
+
+    /// Sets the timestamp of the change.
+    pub fn at(mut self, at: Timestamp) -> Self {
+        self.at = Some(at);
+        self
+    }
+
+    /// Sets the ID of the user or system making the change.
+    pub fn by(mut self, actor: ID) -> Self {
+        self.actor = Some(actor);
+        self
+    }
+
+    /// Sets the reason for the change.
+    pub fn because(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+
+    /// Builds the history entry for a change from `from` to `to`.
+    pub fn into_change<S>(self, from: S, to: S) -> StatusChange<ID, Timestamp, S> {
+        StatusChange {
+            from,
+            to,
+            at: self.at,
+            actor: self.actor,
+            reason: self.reason,
+        }
+    }
+}
+
+impl<ID, Timestamp> Default for ChangeContext<ID, Timestamp> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
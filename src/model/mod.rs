@@ -1,2 +1,6 @@
 pub mod status;
-pub mod booking;
\ No newline at end of file
+pub mod booking;
+pub mod period;
+pub mod history;
+pub mod multi;
+pub mod typestate;
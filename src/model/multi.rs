@@ -0,0 +1,110 @@
+//! Defines `MultiBooking`, a booking made of several line items reserved together, and its
+//! aggregate `MultiBookingStatus`.
+
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::model::booking::Booking;
+use crate::model::status::{BookingStatus, Status};
+use crate::traits::Bookable;
+
+/// The status of a multi-item booking as a whole, derived from the status of its lines.
+///
+/// # Variants:
+///
+/// - `All`: Every line is in the given status, e.g. `All(BookingStatus::Canceled)`.
+/// - `PartiallyCanceled`: Some lines are canceled while the others are not.
+/// - `Mixed`: The lines are in different statuses and none of them is canceled,
+///   e.g. one line is confirmed while another is still pending.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MultiBookingStatus<S = BookingStatus> {
+    /// Every line is in the given status.
+    All(S),
+
+    /// Some lines are canceled while the others are not.
+    PartiallyCanceled,
+
+    /// The lines are in different statuses and none of them is canceled.
+    Mixed,
+}
+
+/// Represents a booking of several items reserved all-or-nothing, such as a trip made of a
+/// flight, a hotel and a rental car, or a group reserving five rooms.
+///
+/// Each line is a regular `Booking` with its own ID, item, quantity and period, so every line
+/// can be stored, queried and checked for conflicts like a single booking. To mix item kinds,
+/// use an enum implementing `Bookable` as `T`.
+///
+/// # Fields
+/// - `id`: Unique identifier of the multi-item booking.
+/// - `user_id`: Optional ID of the user making the booking.
+/// - `lines`: The line items, in the order they were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MultiBooking<T, ID, Timestamp, Metadata, S = BookingStatus> {
+    pub id: ID,
+
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub user_id: Option<ID>,
+    pub lines: Vec<Booking<T, ID, Timestamp, Metadata, S>>,
+}
+
+impl<T, ID, Timestamp, Metadata, S> MultiBooking<T, ID, Timestamp, Metadata, S>
+where
+    T: Bookable,
+    S: Status,
+{
+    /// Returns the IDs of the booked items, in line order.
+    pub fn item_ids(&self) -> Vec<&str> {
+        self.lines.iter().map(|line| line.item_id()).collect()
+    }
+
+    /// Returns the status of the booking as a whole, derived from the status of its lines.
+    ///
+    /// A booking without lines is reported as `MultiBookingStatus::Mixed`.
+    pub fn status(&self) -> MultiBookingStatus<S> {
+        let Some(first) = self.lines.first() else {
+            return MultiBookingStatus::Mixed;
+        };
+
+        if self.lines.iter().all(|line| line.status == first.status) {
+            MultiBookingStatus::All(first.status.clone())
+        } else if self.lines.iter().any(|line| line.is_canceled()) {
+            MultiBookingStatus::PartiallyCanceled
+        } else {
+            MultiBookingStatus::Mixed
+        }
+    }
+
+    /// Returns `true` if at least one line still holds its item.
+    pub fn is_active(&self) -> bool {
+        self.lines.iter().any(|line| line.is_active())
+    }
+
+    /// Returns `true` if no line can be modified anymore.
+    pub fn is_final(&self) -> bool {
+        self.lines.iter().all(|line| line.is_final())
+    }
+
+    /// Returns the lines that have been canceled.
+    pub fn canceled_lines(&self) -> Vec<&Booking<T, ID, Timestamp, Metadata, S>> {
+        self.lines.iter().filter(|line| line.is_canceled()).collect()
+    }
+
+    /// Returns the line with the given booking ID.
+    pub fn line(&self, line_id: &ID) -> Option<&Booking<T, ID, Timestamp, Metadata, S>>
+    where
+        ID: PartialEq,
+    {
+        self.lines.iter().find(|line| &line.id == line_id)
+    }
+
+    /// Returns the line with the given booking ID, mutably.
+    pub fn line_mut(&mut self, line_id: &ID) -> Option<&mut Booking<T, ID, Timestamp, Metadata, S>>
+    where
+        ID: PartialEq,
+    {
+        self.lines.iter_mut().find(|line| &line.id == line_id)
+    }
+}
@@ -0,0 +1,69 @@
+//! Defines `BookingPeriod`, the interval of time an item is reserved for.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::timestamp::BookingTime;
+
+/// Represents the period an item is actually reserved for, such as the nights of a hotel stay
+/// or the slot of a meeting room.
+///
+/// The period is half-open: it includes `start` but not `end`, so back-to-back periods
+/// (e.g. a check-out and a check-in on the same day) do not overlap.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::model::period::BookingPeriod;
+///
+/// let first = BookingPeriod::new(10, 20);
+/// let second = BookingPeriod::new(20, 30);
+/// let third = BookingPeriod::new(15, 25);
+///
+/// assert!(!first.overlaps(&second));
+/// assert!(first.overlaps(&third));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BookingPeriod<Timestamp> {
+    /// Start of the reserved period (inclusive).
+    pub start: Timestamp,
+
+    /// End of the reserved period (exclusive).
+    pub end: Timestamp,
+}
+
+impl<Timestamp> BookingPeriod<Timestamp> {
+    /// Creates a new period from `start` (inclusive) to `end` (exclusive).
+    pub fn new(start: Timestamp, end: Timestamp) -> Self {
+        Self { start, end }
+    }
+}
+
+impl<Timestamp: PartialOrd> BookingPeriod<Timestamp> {
+    /// Returns `true` if the period ends after it starts.
+    pub fn is_valid(&self) -> bool {
+        self.start < self.end
+    }
+
+    /// Returns `true` if the given timestamp falls within the period.
+    pub fn contains(&self, at: &Timestamp) -> bool {
+        &self.start <= at && at < &self.end
+    }
+
+    /// Returns `true` if both periods share at least one instant.
+    pub fn overlaps(&self, other: &BookingPeriod<Timestamp>) -> bool {
+        self.start < other.end && other.start < self.end
+    }
+}
+
+impl<Timestamp: BookingTime> BookingPeriod<Timestamp> {
+    /// Creates a period starting at `start` and lasting `length`, or `None` on overflow.
+    pub fn starting_at(start: Timestamp, length: &Timestamp::Duration) -> Option<Self> {
+        let end = start.checked_plus(length)?;
+        Some(Self { start, end })
+    }
+
+    /// Returns the length of the period, or `None` if it ends before it starts.
+    pub fn duration(&self) -> Option<Timestamp::Duration> {
+        self.end.elapsed_since(&self.start)
+    }
+}
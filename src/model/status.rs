@@ -1,6 +1,121 @@
-//! Contains the various statuses a booking can have, such as `Pending`, `Confirmed`, and `Canceled`.
+//! Contains the various statuses a booking can have, such as `Pending`, `Confirmed`, and `Canceled`,
+//! and the `Status` trait for defining custom status sets.
 
+use alloc::format;
+use core::fmt::Debug;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use crate::error::BookingError;
+
+/// The `Status` trait describes a set of booking statuses, so `Booking` and `BookingManager` can
+/// work with a domain-specific enum instead of `BookingStatus`, which is the default.
+///
+/// A status set classifies its statuses as active (holding the booked item) or final (no longer
+/// modifiable), and defines which transitions are allowed; `TransitionPolicy::default()` is built
+/// from `can_transition_to`.
+///
+/// Operations named after a built-in status, such as `BookingManager::confirm_booking`,
+/// `try_hold` or the expiry sweeper, move bookings to the status returned by
+/// `from_booking_status`. They fail with `BookingError::InvalidStatus` if the set has no such
+/// status.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::model::status::{BookingStatus, Status};
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum VisitStatus {
+///     Scheduled,
+///     CheckedIn,
+///     InProgress,
+///     Completed,
+///     NoShow,
+///     Canceled,
+/// }
+///
+/// impl Status for VisitStatus {
+///     const ALL: &'static [Self] = &[
+///         VisitStatus::Scheduled,
+///         VisitStatus::CheckedIn,
+///         VisitStatus::InProgress,
+///         VisitStatus::Completed,
+///         VisitStatus::NoShow,
+///         VisitStatus::Canceled,
+///     ];
+///
+///     fn is_active(&self) -> bool {
+///         matches!(self, VisitStatus::Scheduled | VisitStatus::CheckedIn | VisitStatus::InProgress)
+///     }
+///
+///     fn is_final(&self) -> bool {
+///         !self.is_active()
+///     }
+///
+///     fn can_transition_to(&self, next: &Self) -> bool {
+///         use VisitStatus::*;
+///         matches!(
+///             (self, next),
+///             (Scheduled, CheckedIn | NoShow | Canceled) | (CheckedIn, InProgress) | (InProgress, Completed)
+///         )
+///     }
+///
+///     fn from_booking_status(status: &BookingStatus) -> Option<Self> {
+///         match status {
+///             BookingStatus::Pending => Some(VisitStatus::Scheduled),
+///             BookingStatus::Completed => Some(VisitStatus::Completed),
+///             BookingStatus::Canceled => Some(VisitStatus::Canceled),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// assert!(VisitStatus::Scheduled.can_transition_to(&VisitStatus::NoShow));
+/// assert!(VisitStatus::NoShow.is_final());
+/// ```
+pub trait Status: Clone + PartialEq + Debug + 'static {
+    /// Every status of the set.
+    const ALL: &'static [Self];
+
+    /// Returns `true` if a booking in this status holds its item.
+    fn is_active(&self) -> bool;
+
+    /// Returns `true` if a booking in this status can no longer be modified.
+    fn is_final(&self) -> bool;
+
+    /// Validates if a transition from `self` to `next` is allowed.
+    fn can_transition_to(&self, next: &Self) -> bool;
+
+    /// Returns `true` if a booking can be created in this status.
+    ///
+    /// Defaults to the statuses playing the role of `Pending`, `OnHold` or `Waitlisted`.
+    fn is_initial(&self) -> bool {
+        [BookingStatus::Pending, BookingStatus::OnHold, BookingStatus::Waitlisted]
+            .iter()
+            .any(|status| Self::from_booking_status(status).as_ref() == Some(self))
+    }
+
+    /// Returns the status of this set playing the role of the built-in `status`, if any.
+    ///
+    /// Defaults to `None` for every status.
+    fn from_booking_status(status: &BookingStatus) -> Option<Self> {
+        let _ = status;
+        None
+    }
+
+    /// Like `from_booking_status`, but returns `BookingError::InvalidStatus` if this set has no
+    /// status playing the role of `status`.
+    fn try_from_booking_status(status: &BookingStatus) -> Result<Self, BookingError> {
+        Self::from_booking_status(status)
+            .ok_or_else(|| BookingError::new_invalid_status(&format!("status set has no {:?} status", status)))
+    }
+
+    /// Builds the error returned when a transition from `self` to `next` is not allowed.
+    ///
+    /// Defaults to `BookingError::InvalidStatus` naming both statuses.
+    fn transition_error(&self, next: &Self) -> BookingError {
+        BookingError::new_invalid_status(&format!("transition from {:?} to {:?} is not allowed", self, next))
+    }
+}
 
 /// Enum representing the various states a booking can have.
 ///
@@ -15,6 +130,12 @@ use serde::{Deserialize, Serialize};
 /// - `Canceled`: The booking has been canceled by the user or the system.
 /// - `Expired`: The booking was not confirmed in time and has expired.
 /// - `Failed`: The booking was not confirmed in time and has failed.
+/// - `Completed`: The booking has been fulfilled or used successfully.
+/// - `Waitlisted`: The item was unavailable and the booking waits for it to free up.
+/// - `OnHold`: The item is held for a short time, e.g. while payment runs, until `expires_at`.
+/// - `CheckedIn`: The guest has arrived and is using the item, e.g. checked into the room.
+/// - `NoShow`: The guest never arrived for a confirmed booking.
+/// - `Refunded`: The confirmed booking was called off and its payment returned.
 ///
 /// # Example:
 ///
@@ -29,7 +150,8 @@ use serde::{Deserialize, Serialize};
 ///     println!("The booking is confirmed.");
 /// }
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum BookingStatus {
     /// The booking has been created but not yet confirmed.
     Pending,
@@ -48,9 +170,46 @@ pub enum BookingStatus {
 
     /// The booking has been fulfilled or used successfully.
     Completed,
+
+    /// The item was unavailable and the booking waits for it to free up.
+    Waitlisted,
+
+    /// The item is held for a short time, e.g. while payment runs, until `expires_at`.
+    OnHold,
+
+    /// The guest has arrived and is using the item, e.g. checked into the room.
+    CheckedIn,
+
+    /// The guest never arrived for a confirmed booking.
+    NoShow,
+
+    /// The confirmed booking was called off and its payment returned.
+    Refunded,
 }
 
 impl BookingStatus {
+    /// Returns `true` if a booking in this status holds its item, i.e. `Pending`, `Confirmed`,
+    /// `OnHold` or `CheckedIn`.
+    pub fn is_active(&self) -> bool {
+        matches!(
+            self,
+            BookingStatus::Pending | BookingStatus::Confirmed | BookingStatus::OnHold | BookingStatus::CheckedIn
+        )
+    }
+
+    /// Returns `true` if a booking in this status can no longer be modified.
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            BookingStatus::Canceled |
+            BookingStatus::Expired |
+            BookingStatus::Failed |
+            BookingStatus::Completed |
+            BookingStatus::NoShow |
+            BookingStatus::Refunded
+        )
+    }
+
     /// Validates if a transition from `self` to `next` is allowed.
     pub fn can_transition_to(&self, next: &BookingStatus) -> bool {
         use BookingStatus::*;
@@ -62,19 +221,76 @@ impl BookingStatus {
             | (Pending, Expired)
             | (Pending, Failed) => true,
 
-            // Confirmed → Completed, Canceled
-            (Confirmed, Completed) | (Confirmed, Canceled) => true,
+            // OnHold → Pending or Confirmed (conversion), Canceled (release), or Expired
+            (OnHold, Pending)
+            | (OnHold, Confirmed)
+            | (OnHold, Canceled)
+            | (OnHold, Expired) => true,
+
+            // Waitlisted → Pending (promotion), Canceled, or Expired
+            (Waitlisted, Pending)
+            | (Waitlisted, Canceled)
+            | (Waitlisted, Expired) => true,
+
+            // Confirmed → Completed, Canceled, CheckedIn (arrival), NoShow, or Refunded
+            (Confirmed, Completed)
+            | (Confirmed, Canceled)
+            | (Confirmed, CheckedIn)
+            | (Confirmed, NoShow)
+            | (Confirmed, Refunded) => true,
+
+            // CheckedIn → Completed (departure)
+            (CheckedIn, Completed) => true,
 
             // Completed = final state
             (Completed, _) => false,
 
-            // Canceled, Expired, Failed = final states
+            // Canceled, Expired, Failed, NoShow, Refunded = final states
             (Canceled, _) => false,
             (Expired, _) => false,
             (Failed, _) => false,
+            (NoShow, _) => false,
+            (Refunded, _) => false,
 
             // Prevent no-op transition unless explicitly allowed
             (current, next) => current == next,
         }
     }
 }
+
+impl Status for BookingStatus {
+    const ALL: &'static [Self] = &[
+        BookingStatus::Pending,
+        BookingStatus::Confirmed,
+        BookingStatus::Canceled,
+        BookingStatus::Expired,
+        BookingStatus::Failed,
+        BookingStatus::Completed,
+        BookingStatus::Waitlisted,
+        BookingStatus::OnHold,
+        BookingStatus::CheckedIn,
+        BookingStatus::NoShow,
+        BookingStatus::Refunded,
+    ];
+
+    fn is_active(&self) -> bool {
+        BookingStatus::is_active(self)
+    }
+
+    fn is_final(&self) -> bool {
+        BookingStatus::is_final(self)
+    }
+
+    fn can_transition_to(&self, next: &Self) -> bool {
+        BookingStatus::can_transition_to(self, next)
+    }
+
+    fn from_booking_status(status: &BookingStatus) -> Option<Self> {
+        Some(status.clone())
+    }
+
+    /// Returns `BookingError::InvalidStatusTransition`.
+    fn transition_error(&self, next: &Self) -> BookingError {
+        BookingError::new_invalid_transition(self.clone(), next.clone())
+    }
+}
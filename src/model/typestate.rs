@@ -0,0 +1,580 @@
+//! Typestate wrapper around `Booking`, checking status transitions at compile time.
+//!
+//! A `TypedBooking<S, ...>` is a `Booking` known to be in the status described by the marker
+//! type `S`, e.g. `TypedBooking<Pending, ...>`. Transitions consume the booking and return it in
+//! its new status, and only exist for the moves allowed by `BookingStatus::can_transition_to`:
+//!
+//! ```rust
+//! use booking_kit::model::typestate::{Completed, TypedBooking};
+//! use booking_kit::traits::Bookable;
+//!
+//! #[derive(Debug, Clone, PartialEq)]
+//! struct Room;
+//! impl Bookable for Room {
+//!     fn id(&self) -> &str { "R101" }
+//!     fn is_available(&self) -> bool { true }
+//! }
+//!
+//! let booking = TypedBooking::create(1, None, Room, 0_i64, None, None::<()>);
+//! let completed: TypedBooking<Completed, _, _, _, _> = booking.confirm().complete();
+//! assert!(completed.is_completed());
+//! ```
+//!
+//! Confirming a canceled booking does not compile:
+//!
+//! ```rust,compile_fail
+//! use booking_kit::model::typestate::TypedBooking;
+//! use booking_kit::traits::Bookable;
+//!
+//! struct Room;
+//! impl Bookable for Room {
+//!     fn id(&self) -> &str { "R101" }
+//!     fn is_available(&self) -> bool { true }
+//! }
+//!
+//! let booking = TypedBooking::create(1, None, Room, 0_i64, None, None::<()>);
+//! booking.cancel().confirm();
+//! ```
+//!
+//! Typed bookings convert losslessly to and from `Booking`, so repositories, events and serde
+//! keep working with the dynamic type. `AnyBooking` turns a `Booking` loaded from storage into
+//! the typed booking matching its status.
+
+use alloc::format;
+use core::marker::PhantomData;
+use core::ops::Deref;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::error::BookingError;
+use crate::manager::BookingManager;
+use crate::model::booking::Booking;
+use crate::model::history::ChangeContext;
+use crate::model::status::BookingStatus;
+use crate::traits::Bookable;
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A marker type standing for one `BookingStatus` in a `TypedBooking`.
+///
+/// This trait is sealed: the markers are the types defined in this module.
+pub trait BookingState: sealed::Sealed {
+    /// The status a `TypedBooking` with this marker is in.
+    const STATUS: BookingStatus;
+}
+
+/// Implemented by a marker for each marker it may transition to, mirroring
+/// `BookingStatus::can_transition_to` except for no-op transitions to the same status.
+/// `tests/typestate_test.rs` checks that the two tables agree.
+pub trait TransitionTo<To: BookingState>: BookingState {}
+
+/// Marker for `BookingStatus::Pending`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Pending;
+
+/// Marker for `BookingStatus::Confirmed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Confirmed;
+
+/// Marker for `BookingStatus::Canceled`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Canceled;
+
+/// Marker for `BookingStatus::Expired`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Expired;
+
+/// Marker for `BookingStatus::Failed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Failed;
+
+/// Marker for `BookingStatus::Completed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Completed;
+
+/// Marker for `BookingStatus::Waitlisted`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Waitlisted;
+
+/// Marker for `BookingStatus::OnHold`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OnHold;
+
+/// Marker for `BookingStatus::CheckedIn`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CheckedIn;
+
+/// Marker for `BookingStatus::NoShow`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NoShow;
+
+/// Marker for `BookingStatus::Refunded`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Refunded;
+
+impl sealed::Sealed for Pending {}
+impl sealed::Sealed for Confirmed {}
+impl sealed::Sealed for Canceled {}
+impl sealed::Sealed for Expired {}
+impl sealed::Sealed for Failed {}
+impl sealed::Sealed for Completed {}
+impl sealed::Sealed for Waitlisted {}
+impl sealed::Sealed for OnHold {}
+impl sealed::Sealed for CheckedIn {}
+impl sealed::Sealed for NoShow {}
+impl sealed::Sealed for Refunded {}
+
+impl BookingState for Pending {
+    const STATUS: BookingStatus = BookingStatus::Pending;
+}
+
+impl BookingState for Confirmed {
+    const STATUS: BookingStatus = BookingStatus::Confirmed;
+}
+
+impl BookingState for Canceled {
+    const STATUS: BookingStatus = BookingStatus::Canceled;
+}
+
+impl BookingState for Expired {
+    const STATUS: BookingStatus = BookingStatus::Expired;
+}
+
+impl BookingState for Failed {
+    const STATUS: BookingStatus = BookingStatus::Failed;
+}
+
+impl BookingState for Completed {
+    const STATUS: BookingStatus = BookingStatus::Completed;
+}
+
+impl BookingState for Waitlisted {
+    const STATUS: BookingStatus = BookingStatus::Waitlisted;
+}
+
+impl BookingState for OnHold {
+    const STATUS: BookingStatus = BookingStatus::OnHold;
+}
+
+impl BookingState for CheckedIn {
+    const STATUS: BookingStatus = BookingStatus::CheckedIn;
+}
+
+impl BookingState for NoShow {
+    const STATUS: BookingStatus = BookingStatus::NoShow;
+}
+
+impl BookingState for Refunded {
+    const STATUS: BookingStatus = BookingStatus::Refunded;
+}
+
+// Pending → Confirmed, Canceled, Expired, or Failed
+impl TransitionTo<Confirmed> for Pending {}
+impl TransitionTo<Canceled> for Pending {}
+impl TransitionTo<Expired> for Pending {}
+impl TransitionTo<Failed> for Pending {}
+
+// OnHold → Pending or Confirmed (conversion), Canceled (release), or Expired
+impl TransitionTo<Pending> for OnHold {}
+impl TransitionTo<Confirmed> for OnHold {}
+impl TransitionTo<Canceled> for OnHold {}
+impl TransitionTo<Expired> for OnHold {}
+
+// Waitlisted → Pending (promotion), Canceled, or Expired
+impl TransitionTo<Pending> for Waitlisted {}
+impl TransitionTo<Canceled> for Waitlisted {}
+impl TransitionTo<Expired> for Waitlisted {}
+
+// Confirmed → Completed, Canceled, CheckedIn (arrival), NoShow, or Refunded
+impl TransitionTo<Completed> for Confirmed {}
+impl TransitionTo<Canceled> for Confirmed {}
+impl TransitionTo<CheckedIn> for Confirmed {}
+impl TransitionTo<NoShow> for Confirmed {}
+impl TransitionTo<Refunded> for Confirmed {}
+
+// CheckedIn → Completed (departure)
+impl TransitionTo<Completed> for CheckedIn {}
+
+/// A `Booking` whose status is fixed by the marker type `S`.
+///
+/// Read access to the booking goes through `Deref`; the status can only change through the
+/// transition methods, which record the change in the booking's history like
+/// `BookingManager::transition_with`. Use `into_booking` or `From` to store the booking, and
+/// `TryFrom` or `AnyBooking` to get a typed booking back.
+///
+/// Transitions follow `BookingStatus::can_transition_to`; a custom `TransitionPolicy` is not
+/// consulted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedBooking<S, T, ID, Timestamp, Metadata> {
+    booking: Booking<T, ID, Timestamp, Metadata>,
+    state: PhantomData<S>,
+}
+
+impl<S: BookingState, T, ID, Timestamp, Metadata> TypedBooking<S, T, ID, Timestamp, Metadata> {
+    /// Returns the status of the booking, i.e. `S::STATUS`.
+    pub fn status(&self) -> BookingStatus {
+        S::STATUS
+    }
+
+    /// Returns the underlying booking.
+    pub fn as_booking(&self) -> &Booking<T, ID, Timestamp, Metadata> {
+        &self.booking
+    }
+
+    /// Unwraps the underlying booking, e.g. to store it in a repository.
+    pub fn into_booking(self) -> Booking<T, ID, Timestamp, Metadata> {
+        self.booking
+    }
+
+    /// Moves the booking to the status of `To`, recording the change described by `context` in
+    /// its history and incrementing its `version`.
+    pub fn transition_with<To>(mut self, context: ChangeContext<ID, Timestamp>) -> TypedBooking<To, T, ID, Timestamp, Metadata>
+    where
+        S: TransitionTo<To>,
+        To: BookingState,
+    {
+        self.booking.status = To::STATUS;
+        self.booking.history.push(context.into_change(S::STATUS, To::STATUS));
+        self.booking.version += 1;
+        TypedBooking {
+            booking: self.booking,
+            state: PhantomData,
+        }
+    }
+
+    /// Moves the booking to the status of `To` with an empty change context.
+    fn transition<To>(self) -> TypedBooking<To, T, ID, Timestamp, Metadata>
+    where
+        S: TransitionTo<To>,
+        To: BookingState,
+    {
+        self.transition_with(ChangeContext::new())
+    }
+}
+
+impl<T, ID, Timestamp, Metadata> TypedBooking<Pending, T, ID, Timestamp, Metadata> {
+    /// Creates a new pending booking, like `BookingManager::create`.
+    pub fn create(
+        booking_id: ID,
+        user_id: Option<ID>,
+        item: T,
+        created_at: Timestamp,
+        expires_at: Option<Timestamp>,
+        metadata: Option<Metadata>,
+    ) -> Self
+    where
+        T: Bookable,
+        ID: Clone,
+    {
+        Self {
+            booking: BookingManager::create(booking_id, user_id, item, created_at, expires_at, metadata),
+            state: PhantomData,
+        }
+    }
+
+    /// Marks the booking as `Confirmed`.
+    pub fn confirm(self) -> TypedBooking<Confirmed, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Marks the booking as `Canceled`.
+    pub fn cancel(self) -> TypedBooking<Canceled, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Marks the booking as `Expired`.
+    pub fn expire(self) -> TypedBooking<Expired, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Marks the booking as `Failed`.
+    pub fn fail(self) -> TypedBooking<Failed, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+}
+
+impl<T, ID, Timestamp, Metadata> TypedBooking<OnHold, T, ID, Timestamp, Metadata> {
+    /// Converts the hold into a `Pending` booking.
+    pub fn convert(self) -> TypedBooking<Pending, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Converts the hold directly into a `Confirmed` booking.
+    pub fn confirm(self) -> TypedBooking<Confirmed, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Releases the hold, marking it as `Canceled`.
+    pub fn cancel(self) -> TypedBooking<Canceled, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Marks the hold as `Expired`.
+    pub fn expire(self) -> TypedBooking<Expired, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+}
+
+impl<T, ID, Timestamp, Metadata> TypedBooking<Waitlisted, T, ID, Timestamp, Metadata> {
+    /// Promotes the booking off the waitlist, marking it as `Pending`.
+    pub fn promote(self) -> TypedBooking<Pending, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Marks the booking as `Canceled`.
+    pub fn cancel(self) -> TypedBooking<Canceled, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Marks the booking as `Expired`.
+    pub fn expire(self) -> TypedBooking<Expired, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+}
+
+impl<T, ID, Timestamp, Metadata> TypedBooking<Confirmed, T, ID, Timestamp, Metadata> {
+    /// Marks the booking as `Completed`.
+    pub fn complete(self) -> TypedBooking<Completed, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Marks the booking as `Canceled`.
+    pub fn cancel(self) -> TypedBooking<Canceled, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Marks the guest as arrived, i.e. `CheckedIn`.
+    pub fn check_in(self) -> TypedBooking<CheckedIn, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Marks the booking as `NoShow`.
+    pub fn mark_no_show(self) -> TypedBooking<NoShow, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+
+    /// Marks the booking as `Refunded`.
+    pub fn refund(self) -> TypedBooking<Refunded, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+}
+
+impl<T, ID, Timestamp, Metadata> TypedBooking<CheckedIn, T, ID, Timestamp, Metadata> {
+    /// Marks the stay as over, i.e. `Completed`.
+    pub fn complete(self) -> TypedBooking<Completed, T, ID, Timestamp, Metadata> {
+        self.transition()
+    }
+}
+
+impl<S, T, ID, Timestamp, Metadata> Deref for TypedBooking<S, T, ID, Timestamp, Metadata> {
+    type Target = Booking<T, ID, Timestamp, Metadata>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.booking
+    }
+}
+
+impl<S, T, ID, Timestamp, Metadata> From<TypedBooking<S, T, ID, Timestamp, Metadata>> for Booking<T, ID, Timestamp, Metadata> {
+    fn from(typed: TypedBooking<S, T, ID, Timestamp, Metadata>) -> Self {
+        typed.booking
+    }
+}
+
+impl<S: BookingState, T, ID, Timestamp, Metadata> TryFrom<Booking<T, ID, Timestamp, Metadata>> for TypedBooking<S, T, ID, Timestamp, Metadata> {
+    type Error = BookingError;
+
+    /// Wraps `booking` if it is in the status of `S`.
+    ///
+    /// Returns `BookingError::InvalidStatus` otherwise; use `AnyBooking` to accept any status.
+    fn try_from(booking: Booking<T, ID, Timestamp, Metadata>) -> Result<Self, Self::Error> {
+        if booking.status != S::STATUS {
+            return Err(BookingError::new_invalid_status(&format!(
+                "expected {:?}, found {:?}",
+                S::STATUS,
+                booking.status
+            )));
+        }
+        Ok(Self {
+            booking,
+            state: PhantomData,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S, T, ID, Timestamp, Metadata> Serialize for TypedBooking<S, T, ID, Timestamp, Metadata>
+where
+    Booking<T, ID, Timestamp, Metadata>: Serialize,
+{
+    /// Serializes the booking exactly like the underlying `Booking`.
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        self.booking.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, S: BookingState, T, ID, Timestamp, Metadata> Deserialize<'de> for TypedBooking<S, T, ID, Timestamp, Metadata>
+where
+    Booking<T, ID, Timestamp, Metadata>: Deserialize<'de>,
+{
+    /// Deserializes a `Booking`, failing if it is not in the status of `S`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let booking = Booking::deserialize(deserializer)?;
+        TypedBooking::try_from(booking).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A `TypedBooking` in whichever status a dynamic `Booking` is in, e.g. one loaded from a
+/// repository.
+///
+/// # Variants:
+///
+/// - `Pending`: A `TypedBooking<Pending, ...>`.
+/// - `Confirmed`: A `TypedBooking<Confirmed, ...>`.
+/// - `Canceled`: A `TypedBooking<Canceled, ...>`.
+/// - `Expired`: A `TypedBooking<Expired, ...>`.
+/// - `Failed`: A `TypedBooking<Failed, ...>`.
+/// - `Completed`: A `TypedBooking<Completed, ...>`.
+/// - `Waitlisted`: A `TypedBooking<Waitlisted, ...>`.
+/// - `OnHold`: A `TypedBooking<OnHold, ...>`.
+/// - `CheckedIn`: A `TypedBooking<CheckedIn, ...>`.
+/// - `NoShow`: A `TypedBooking<NoShow, ...>`.
+/// - `Refunded`: A `TypedBooking<Refunded, ...>`.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::manager::BookingManager;
+/// use booking_kit::model::booking::Booking;
+/// use booking_kit::model::typestate::AnyBooking;
+/// use booking_kit::traits::Bookable;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Room;
+/// impl Bookable for Room {
+///     fn id(&self) -> &str { "R101" }
+///     fn is_available(&self) -> bool { true }
+/// }
+///
+/// let stored = BookingManager::create(1, None, Room, 0_i64, None, None::<()>);
+///
+/// let confirmed: Booking<_, _, _, _> = match AnyBooking::from(stored) {
+///     AnyBooking::Pending(booking) => booking.confirm().into(),
+///     other => other.into(),
+/// };
+/// assert!(confirmed.is_confirmed());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnyBooking<T, ID, Timestamp, Metadata> {
+    Pending(TypedBooking<Pending, T, ID, Timestamp, Metadata>),
+    Confirmed(TypedBooking<Confirmed, T, ID, Timestamp, Metadata>),
+    Canceled(TypedBooking<Canceled, T, ID, Timestamp, Metadata>),
+    Expired(TypedBooking<Expired, T, ID, Timestamp, Metadata>),
+    Failed(TypedBooking<Failed, T, ID, Timestamp, Metadata>),
+    Completed(TypedBooking<Completed, T, ID, Timestamp, Metadata>),
+    Waitlisted(TypedBooking<Waitlisted, T, ID, Timestamp, Metadata>),
+    OnHold(TypedBooking<OnHold, T, ID, Timestamp, Metadata>),
+    CheckedIn(TypedBooking<CheckedIn, T, ID, Timestamp, Metadata>),
+    NoShow(TypedBooking<NoShow, T, ID, Timestamp, Metadata>),
+    Refunded(TypedBooking<Refunded, T, ID, Timestamp, Metadata>),
+}
+
+impl<T, ID, Timestamp, Metadata> AnyBooking<T, ID, Timestamp, Metadata> {
+    /// Returns the status of the booking.
+    pub fn status(&self) -> BookingStatus {
+        self.as_booking().status.clone()
+    }
+
+    /// Returns the underlying booking.
+    pub fn as_booking(&self) -> &Booking<T, ID, Timestamp, Metadata> {
+        match self {
+            AnyBooking::Pending(booking) => booking.as_booking(),
+            AnyBooking::Confirmed(booking) => booking.as_booking(),
+            AnyBooking::Canceled(booking) => booking.as_booking(),
+            AnyBooking::Expired(booking) => booking.as_booking(),
+            AnyBooking::Failed(booking) => booking.as_booking(),
+            AnyBooking::Completed(booking) => booking.as_booking(),
+            AnyBooking::Waitlisted(booking) => booking.as_booking(),
+            AnyBooking::OnHold(booking) => booking.as_booking(),
+            AnyBooking::CheckedIn(booking) => booking.as_booking(),
+            AnyBooking::NoShow(booking) => booking.as_booking(),
+            AnyBooking::Refunded(booking) => booking.as_booking(),
+        }
+    }
+
+    /// Unwraps the underlying booking.
+    pub fn into_booking(self) -> Booking<T, ID, Timestamp, Metadata> {
+        match self {
+            AnyBooking::Pending(booking) => booking.into_booking(),
+            AnyBooking::Confirmed(booking) => booking.into_booking(),
+            AnyBooking::Canceled(booking) => booking.into_booking(),
+            AnyBooking::Expired(booking) => booking.into_booking(),
+            AnyBooking::Failed(booking) => booking.into_booking(),
+            AnyBooking::Completed(booking) => booking.into_booking(),
+            AnyBooking::Waitlisted(booking) => booking.into_booking(),
+            AnyBooking::OnHold(booking) => booking.into_booking(),
+            AnyBooking::CheckedIn(booking) => booking.into_booking(),
+            AnyBooking::NoShow(booking) => booking.into_booking(),
+            AnyBooking::Refunded(booking) => booking.into_booking(),
+        }
+    }
+}
+
+impl<T, ID, Timestamp, Metadata> From<Booking<T, ID, Timestamp, Metadata>> for AnyBooking<T, ID, Timestamp, Metadata> {
+    fn from(booking: Booking<T, ID, Timestamp, Metadata>) -> Self {
+        fn typed<S, T, ID, Timestamp, Metadata>(booking: Booking<T, ID, Timestamp, Metadata>) -> TypedBooking<S, T, ID, Timestamp, Metadata> {
+            TypedBooking {
+                booking,
+                state: PhantomData,
+            }
+        }
+
+        match booking.status {
+            BookingStatus::Pending => AnyBooking::Pending(typed(booking)),
+            BookingStatus::Confirmed => AnyBooking::Confirmed(typed(booking)),
+            BookingStatus::Canceled => AnyBooking::Canceled(typed(booking)),
+            BookingStatus::Expired => AnyBooking::Expired(typed(booking)),
+            BookingStatus::Failed => AnyBooking::Failed(typed(booking)),
+            BookingStatus::Completed => AnyBooking::Completed(typed(booking)),
+            BookingStatus::Waitlisted => AnyBooking::Waitlisted(typed(booking)),
+            BookingStatus::OnHold => AnyBooking::OnHold(typed(booking)),
+            BookingStatus::CheckedIn => AnyBooking::CheckedIn(typed(booking)),
+            BookingStatus::NoShow => AnyBooking::NoShow(typed(booking)),
+            BookingStatus::Refunded => AnyBooking::Refunded(typed(booking)),
+        }
+    }
+}
+
+impl<T, ID, Timestamp, Metadata> From<AnyBooking<T, ID, Timestamp, Metadata>> for Booking<T, ID, Timestamp, Metadata> {
+    fn from(any: AnyBooking<T, ID, Timestamp, Metadata>) -> Self {
+        any.into_booking()
+    }
+}
+
+impl<S, T, ID, Timestamp, Metadata> From<TypedBooking<S, T, ID, Timestamp, Metadata>> for AnyBooking<T, ID, Timestamp, Metadata> {
+    fn from(typed: TypedBooking<S, T, ID, Timestamp, Metadata>) -> Self {
+        AnyBooking::from(typed.booking)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T, ID, Timestamp, Metadata> Serialize for AnyBooking<T, ID, Timestamp, Metadata>
+where
+    Booking<T, ID, Timestamp, Metadata>: Serialize,
+{
+    /// Serializes the booking exactly like the underlying `Booking`.
+    fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        self.as_booking().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T, ID, Timestamp, Metadata> Deserialize<'de> for AnyBooking<T, ID, Timestamp, Metadata>
+where
+    Booking<T, ID, Timestamp, Metadata>: Deserialize<'de>,
+{
+    /// Deserializes a `Booking` in any status.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Booking::deserialize(deserializer).map(AnyBooking::from)
+    }
+}
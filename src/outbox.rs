@@ -0,0 +1,179 @@
+//! Transactional outbox for booking events, and the relay delivering them to a publisher.
+//!
+//! A repository implementing `BookingOutbox` stores each booking write together with the
+//! `BookingEvent` it produced, so an event is never lost nor published for a write that did not
+//! happen. An `OutboxRelay` then drains the stored events to an `EventPublisher`.
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+#[cfg(feature = "std")]
+use std::collections::HashSet;
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::error::BookingError;
+use crate::events::BookingEvent;
+use crate::model::status::BookingStatus;
+
+/// An event waiting in the outbox.
+///
+/// # Fields
+/// - `id`: Unique, increasing ID of the message. Delivery is at-least-once, so consumers should
+///   use it to discard messages they have already handled.
+/// - `event`: The event to publish.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OutboxMessage<ID, Timestamp, S = BookingStatus> {
+    pub id: u64,
+    pub event: BookingEvent<ID, Timestamp, S>,
+}
+
+/// The `BookingOutbox` trait exposes the events a repository stored alongside its writes.
+pub trait BookingOutbox<ID, Timestamp, S = BookingStatus> {
+    /// Returns up to `limit` undelivered messages, oldest first.
+    fn pending_messages(&self, limit: usize) -> Result<Vec<OutboxMessage<ID, Timestamp, S>>, BookingError>;
+
+    /// Marks a message as delivered so it is no longer returned by `pending_messages`.
+    /// Acknowledging an unknown or already delivered message is not an error.
+    fn acknowledge(&self, message_id: u64) -> Result<(), BookingError>;
+}
+
+/// The `EventPublisher` trait delivers outbox messages to downstream systems, e.g. a message broker.
+pub trait EventPublisher<ID, Timestamp, S = BookingStatus> {
+    /// Publishes a message. An error leaves the message in the outbox to be retried.
+    fn publish(&self, message: &OutboxMessage<ID, Timestamp, S>) -> Result<(), BookingError>;
+}
+
+impl<ID, Timestamp, S, P> EventPublisher<ID, Timestamp, S> for Box<P>
+where
+    P: EventPublisher<ID, Timestamp, S> + ?Sized,
+{
+    fn publish(&self, message: &OutboxMessage<ID, Timestamp, S>) -> Result<(), BookingError> {
+        (**self).publish(message)
+    }
+}
+
+/// A publisher skipping messages whose ID it has already published, for consumers that need to
+/// turn at-least-once delivery into effectively-once handling within a process. Requires the
+/// `std` feature.
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct DeduplicatingPublisher<P> {
+    inner: P,
+    published: Mutex<HashSet<u64>>,
+}
+
+#[cfg(feature = "std")]
+impl<P> DeduplicatingPublisher<P> {
+    /// Wraps `inner`, forwarding each message ID to it at most once.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            published: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Returns the wrapped publisher.
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+}
+
+#[cfg(feature = "std")]
+impl<ID, Timestamp, S, P> EventPublisher<ID, Timestamp, S> for DeduplicatingPublisher<P>
+where
+    P: EventPublisher<ID, Timestamp, S>,
+{
+    fn publish(&self, message: &OutboxMessage<ID, Timestamp, S>) -> Result<(), BookingError> {
+        let mut published = self
+            .published
+            .lock()
+            .map_err(|e| BookingError::new_storage_error(&e.to_string()))?;
+        if published.contains(&message.id) {
+            return Ok(());
+        }
+        self.inner.publish(message)?;
+        published.insert(message.id);
+        Ok(())
+    }
+}
+
+/// Outcome of draining an outbox.
+///
+/// # Fields
+/// - `delivered`: IDs of the messages published and acknowledged, in order.
+/// - `failed`: The message that could not be published, with the reason. Draining stops there so
+///   events are delivered in order; the message is retried by the next run.
+#[derive(Debug, Default)]
+pub struct RelayReport {
+    pub delivered: Vec<u64>,
+    pub failed: Option<(u64, BookingError)>,
+}
+
+impl RelayReport {
+    /// Returns `true` if the outbox was drained without failures.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_none()
+    }
+}
+
+/// Drains an outbox to an `EventPublisher` with at-least-once delivery.
+///
+/// Each message is acknowledged only after it has been published, so a crash in between publishes
+/// it again on the next run; consumers deduplicate with `OutboxMessage::id`.
+#[derive(Debug, Clone)]
+pub struct OutboxRelay<P> {
+    publisher: P,
+    batch_size: usize,
+}
+
+impl<P> OutboxRelay<P> {
+    /// Creates a relay publishing to `publisher`, reading 100 messages at a time.
+    pub fn new(publisher: P) -> Self {
+        Self {
+            publisher,
+            batch_size: 100,
+        }
+    }
+
+    /// Returns the relay reading `batch_size` messages at a time (at least one).
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Returns the publisher of this relay.
+    pub fn publisher(&self) -> &P {
+        &self.publisher
+    }
+
+    /// Publishes and acknowledges pending messages until the outbox is empty or a message fails.
+    ///
+    /// Returns an error only if the outbox itself fails; publishing failures are reported in
+    /// `RelayReport::failed`.
+    pub fn relay<O, ID, Timestamp, S>(&self, outbox: &O) -> Result<RelayReport, BookingError>
+    where
+        O: BookingOutbox<ID, Timestamp, S>,
+        P: EventPublisher<ID, Timestamp, S>,
+    {
+        let mut report = RelayReport::default();
+        loop {
+            let batch = outbox.pending_messages(self.batch_size)?;
+            if batch.is_empty() {
+                return Ok(report);
+            }
+
+            for message in batch {
+                if let Err(e) = self.publisher.publish(&message) {
+                    report.failed = Some((message.id, e));
+                    return Ok(report);
+                }
+                outbox.acknowledge(message.id)?;
+                report.delivered.push(message.id);
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Configurable rules describing which status transitions a booking may go through.
+
+#[cfg(feature = "json")]
+use alloc::format;
+#[cfg(feature = "json")]
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::error::BookingError;
+use crate::model::status::{BookingStatus, Status};
+
+/// A single allowed move from one status to another, `BookingStatus` by default.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Transition<S = BookingStatus> {
+    pub from: S,
+    pub to: S,
+}
+
+/// A table of allowed status transitions.
+///
+/// `TransitionPolicy::default()` mirrors `Status::can_transition_to` of the status set, `BookingStatus`
+/// unless another `Status` is given. Domains with a different lifecycle can start from the default
+/// (or from `TransitionPolicy::empty()`) and allow or forbid individual transitions, or load the whole
+/// table from JSON.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::model::status::BookingStatus;
+/// use booking_kit::policy::TransitionPolicy;
+///
+/// // Allow reinstating a canceled booking.
+/// let policy = TransitionPolicy::default().allow(BookingStatus::Canceled, BookingStatus::Pending);
+/// assert!(policy.allows(&BookingStatus::Canceled, &BookingStatus::Pending));
+///
+/// # #[cfg(feature = "json")] {
+/// let policy = TransitionPolicy::from_json(r#"{
+///     "transitions": [
+///         { "from": "Pending", "to": "Confirmed" },
+///         { "from": "Confirmed", "to": "Completed" }
+///     ]
+/// }"#).unwrap();
+/// assert!(!policy.allows(&BookingStatus::Pending, &BookingStatus::Canceled));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TransitionPolicy<S = BookingStatus> {
+    transitions: Vec<Transition<S>>,
+}
+
+impl<S: PartialEq> TransitionPolicy<S> {
+    /// Creates a policy that allows no transitions at all.
+    pub fn empty() -> Self {
+        Self { transitions: Vec::new() }
+    }
+
+    /// Returns the policy with the transition from `from` to `to` allowed.
+    pub fn allow(mut self, from: S, to: S) -> Self {
+        if !self.allows(&from, &to) {
+            self.transitions.push(Transition { from, to });
+        }
+        self
+    }
+
+    /// Returns the policy with the transition from `from` to `to` forbidden.
+    pub fn forbid(mut self, from: S, to: S) -> Self {
+        self.transitions.retain(|t| t.from != from || t.to != to);
+        self
+    }
+
+    /// Returns `true` if the policy allows moving from `from` to `to`.
+    pub fn allows(&self, from: &S, to: &S) -> bool {
+        self.transitions.iter().any(|t| &t.from == from && &t.to == to)
+    }
+
+    /// Validates a transition, returning the error built by `Status::transition_error` if it is not
+    /// allowed: `BookingError::InvalidStatusTransition` for `BookingStatus`.
+    pub fn validate(&self, from: &S, to: &S) -> Result<(), BookingError>
+    where
+        S: Status,
+    {
+        if !self.allows(from, to) {
+            return Err(from.transition_error(to));
+        }
+        Ok(())
+    }
+
+    /// Returns the allowed transitions, in the order they were added.
+    pub fn transitions(&self) -> &[Transition<S>] {
+        &self.transitions
+    }
+
+    /// Serializes the policy to JSON, in the format accepted by `from_json`.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self) -> Result<String, BookingError>
+    where
+        S: Serialize,
+    {
+        serde_json::to_string(self)
+            .map_err(|e| BookingError::new_general_error(&format!("cannot serialize transition policy: {}", e)))
+    }
+}
+
+impl TransitionPolicy {
+    /// Loads a policy from JSON in the form `{"transitions": [{"from": "Pending", "to": "Confirmed"}, ...]}`.
+    ///
+    /// Returns `BookingError::GeneralError` if the JSON is malformed. Requires the `json` feature.
+    /// Policies over another `Status` deserialize with serde directly.
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, BookingError> {
+        serde_json::from_str(json)
+            .map_err(|e| BookingError::new_general_error(&format!("invalid transition policy: {}", e)))
+    }
+}
+
+impl<S: Status> Default for TransitionPolicy<S> {
+    /// Builds the policy matching `Status::can_transition_to`.
+    fn default() -> Self {
+        let mut policy = TransitionPolicy::empty();
+        for from in S::ALL {
+            for to in S::ALL {
+                if from.can_transition_to(to) {
+                    policy = policy.allow(from.clone(), to.clone());
+                }
+            }
+        }
+        policy
+    }
+}
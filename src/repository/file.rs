@@ -0,0 +1,507 @@
+//! Append-only JSON-lines file implementation of `BookingRepository`.
+
+use std::fmt::Display;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::slice;
+use std::sync::{Mutex, MutexGuard};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::error::BookingError;
+use crate::model::booking::Booking;
+use crate::model::status::BookingStatus;
+use crate::repository::{ensure_new_ids, is_related, BookingRepository};
+use crate::traits::Bookable;
+
+/// A single line of the log file.
+///
+/// Inserts and updates carry the whole booking, so replaying a record that is already part of the
+/// snapshot yields the same state.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub(crate) enum LogRecord<T, ID, Timestamp, Metadata, S> {
+    Insert { booking: Booking<T, ID, Timestamp, Metadata, S> },
+    Update { booking: Booking<T, ID, Timestamp, Metadata, S> },
+    Remove { booking_id: ID },
+}
+
+/// An incomplete last line found in the log on open, left by a crash in the middle of a write.
+///
+/// # Fields
+/// - `line`: Line number of the incomplete record, starting at `1`.
+/// - `offset`: Byte offset the log was truncated to, i.e. where the incomplete record started.
+/// - `len`: Number of bytes discarded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TruncatedTail {
+    pub line: usize,
+    pub offset: u64,
+    pub len: usize,
+}
+
+/// A `BookingRepository` persisted to an append-only JSON-lines log.
+///
+/// Every insert, update and removal appends one line to the log and syncs it before returning;
+/// `insert_checked` appends the lines of all its bookings with a single write. A write that fails
+/// is truncated away again, so the failed operation leaves no trace in the log.
+/// On `open`, the snapshot file (if any) and then the log are replayed into an in-memory index
+/// serving all reads. `compact` rewrites the current bookings into the snapshot file and empties
+/// the log, so it does not grow without bounds.
+///
+/// A line missing its trailing newline can only come from a write interrupted by a crash, whose
+/// caller never saw it succeed: `open` drops it, truncates the log to the last complete line, and
+/// reports it through `truncated_tail`. Any other line that cannot be parsed is reported as
+/// `BookingError::StorageError` with its line number.
+///
+/// Intended for small deployments and integration tests; the whole data set is kept in memory.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::manager::BookingManager;
+/// use booking_kit::repository::BookingRepository;
+/// use booking_kit::repository::file::FileBookingRepository;
+/// use booking_kit::traits::Bookable;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// struct Room { id: String }
+/// impl Bookable for Room {
+///     fn id(&self) -> &str { &self.id }
+///     fn is_available(&self) -> bool { true }
+/// }
+///
+/// let path = std::env::temp_dir().join(format!("booking_kit_doc_{}.jsonl", std::process::id()));
+/// # let _ = std::fs::remove_file(&path);
+/// let manager = BookingManager::new(FileBookingRepository::open(&path).unwrap());
+/// manager.create_booking(1, None, Room { id: "R101".into() }, 0_i64, None, None::<()>).unwrap();
+/// manager.confirm_booking(&1).unwrap();
+/// manager.repository().compact().unwrap();
+/// drop(manager);
+///
+/// let reopened = FileBookingRepository::<Room, u32, i64, ()>::open(&path).unwrap();
+/// assert!(reopened.get(&1).unwrap().unwrap().is_confirmed());
+/// # std::fs::remove_file(&path).unwrap();
+/// # std::fs::remove_file(reopened.snapshot_path()).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct FileBookingRepository<T, ID, Timestamp, Metadata, S = BookingStatus> {
+    log: JsonLinesLog<FileState<T, ID, Timestamp, Metadata, S>>,
+}
+
+impl<T, ID, Timestamp, Metadata, S> FileBookingRepository<T, ID, Timestamp, Metadata, S>
+where
+    T: Serialize + DeserializeOwned,
+    ID: PartialEq + Serialize + DeserializeOwned,
+    Timestamp: Serialize + DeserializeOwned,
+    Metadata: Serialize + DeserializeOwned,
+    S: Serialize + DeserializeOwned,
+{
+    /// Opens the log at `path`, creating it if it does not exist, and rebuilds the index from the
+    /// snapshot file next to it (see `snapshot_path`) and the log.
+    ///
+    /// Returns `BookingError::StorageError` if a file cannot be read or a complete line cannot be
+    /// parsed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BookingError> {
+        Ok(Self { log: JsonLinesLog::open(path.as_ref())? })
+    }
+
+    /// Returns the path of the log file.
+    pub fn path(&self) -> &Path {
+        self.log.path()
+    }
+
+    /// Returns the path of the snapshot file: the log path with `.snapshot` appended.
+    pub fn snapshot_path(&self) -> &Path {
+        self.log.snapshot_path()
+    }
+
+    /// Returns the incomplete record dropped from the end of the log on open, if any.
+    pub fn truncated_tail(&self) -> Option<&TruncatedTail> {
+        self.log.truncated_tail()
+    }
+
+    /// Rewrites the current bookings into the snapshot file and empties the log.
+    ///
+    /// The snapshot is written to a temporary file and renamed over the previous one before the
+    /// log is emptied, so a crash at any point leaves files that reopen to the same bookings.
+    pub fn compact(&self) -> Result<(), BookingError> {
+        self.log.compact()
+    }
+
+    /// Returns the size of the log in bytes, e.g. to decide when to `compact`.
+    pub fn log_len(&self) -> Result<u64, BookingError> {
+        self.log.log_len()
+    }
+}
+
+/// The in-memory state of a `JsonLinesLog`: rebuilt from the snapshot file and the records of the
+/// log on open, and written back to the snapshot file by `compact`.
+pub(crate) trait LogState {
+    /// A line of the log.
+    type Record: Serialize + DeserializeOwned;
+
+    /// A line of the snapshot file.
+    type Entry: DeserializeOwned;
+
+    /// Rebuilds the state from the lines of the snapshot file.
+    fn restore(entries: Vec<Self::Entry>) -> Self;
+
+    /// Applies a record of the log. Replaying a record that is already part of the snapshot must
+    /// yield the same state.
+    fn apply(&mut self, record: Self::Record);
+
+    /// Returns the lines of a snapshot file rebuilding the current state, each ending in a newline.
+    fn snapshot(&self) -> Result<String, BookingError>;
+}
+
+/// An append-only JSON-lines log with a snapshot file, replayed into a `LogState` on open.
+///
+/// Shared by `FileBookingRepository` and `file_outbox::FileOutboxRepository`, whose docs
+/// describe the recovery and compaction guarantees.
+#[derive(Debug)]
+pub(crate) struct JsonLinesLog<St> {
+    path: PathBuf,
+    snapshot_path: PathBuf,
+    truncated_tail: Option<TruncatedTail>,
+    file: Mutex<LogFile<St>>,
+}
+
+/// The open log file together with the state it describes, locked as one.
+#[derive(Debug)]
+pub(crate) struct LogFile<St> {
+    log: File,
+    pub(crate) state: St,
+}
+
+impl<St: LogState> JsonLinesLog<St> {
+    /// Opens the log at `path`, creating it if it does not exist, and rebuilds the state from the
+    /// snapshot file next to it and the log, dropping an incomplete last line.
+    pub(crate) fn open(path: &Path) -> Result<Self, BookingError> {
+        let path = path.to_path_buf();
+        let mut snapshot_path = path.clone().into_os_string();
+        snapshot_path.push(".snapshot");
+        let snapshot_path = PathBuf::from(snapshot_path);
+
+        let mut entries = Vec::new();
+        if snapshot_path.exists() {
+            let snapshot = File::open(&snapshot_path).map_err(|e| io_error(&snapshot_path, e))?;
+            for (index, line) in BufReader::new(snapshot).lines().enumerate() {
+                let line = line.map_err(|e| io_error(&snapshot_path, e))?;
+                if !line.trim().is_empty() {
+                    entries.push(parse_line(&snapshot_path, index, &line)?);
+                }
+            }
+        }
+
+        let mut log = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)
+            .map_err(|e| io_error(&path, e))?;
+        let mut content = Vec::new();
+        log.read_to_end(&mut content).map_err(|e| io_error(&path, e))?;
+
+        let complete = content.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+        let mut file = LogFile { log, state: St::restore(entries) };
+        for (index, line) in content[..complete].split(|&b| b == b'\n').enumerate() {
+            let line = std::str::from_utf8(line).map_err(|e| {
+                BookingError::new_storage_error(&format!("{}: line {}: {}", path.display(), index + 1, e))
+            })?;
+            if !line.trim().is_empty() {
+                file.state.apply(parse_line(&path, index, line)?);
+            }
+        }
+
+        let truncated_tail = if complete < content.len() {
+            let offset = complete as u64;
+            file.log.set_len(offset).map_err(|e| io_error(&path, e))?;
+            file.log.sync_data().map_err(|e| io_error(&path, e))?;
+            Some(TruncatedTail {
+                line: content[..complete].iter().filter(|&&b| b == b'\n').count() + 1,
+                offset,
+                len: content.len() - complete,
+            })
+        } else {
+            None
+        };
+
+        Ok(Self {
+            path,
+            snapshot_path,
+            truncated_tail,
+            file: Mutex::new(file),
+        })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(crate) fn snapshot_path(&self) -> &Path {
+        &self.snapshot_path
+    }
+
+    pub(crate) fn truncated_tail(&self) -> Option<&TruncatedTail> {
+        self.truncated_tail.as_ref()
+    }
+
+    /// Rewrites the current state into the snapshot file and empties the log.
+    ///
+    /// The snapshot is written to a temporary file and renamed over the previous one, and the
+    /// rename is synced to the directory before the log is emptied, so a crash at any point leaves
+    /// files that reopen to the same state.
+    pub(crate) fn compact(&self) -> Result<(), BookingError> {
+        let file = self.lock()?;
+
+        let mut temporary = self.snapshot_path.clone().into_os_string();
+        temporary.push(".tmp");
+        let temporary = PathBuf::from(temporary);
+
+        let mut snapshot = File::create(&temporary).map_err(|e| io_error(&temporary, e))?;
+        snapshot.write_all(file.state.snapshot()?.as_bytes()).map_err(|e| io_error(&temporary, e))?;
+        snapshot.sync_all().map_err(|e| io_error(&temporary, e))?;
+        fs::rename(&temporary, &self.snapshot_path).map_err(|e| io_error(&self.snapshot_path, e))?;
+        sync_parent(&self.snapshot_path)?;
+
+        file.log.set_len(0).map_err(|e| io_error(&self.path, e))?;
+        file.log.sync_all().map_err(|e| io_error(&self.path, e))
+    }
+
+    pub(crate) fn log_len(&self) -> Result<u64, BookingError> {
+        let mut file = self.lock()?;
+        file.log.seek(SeekFrom::End(0)).map_err(|e| io_error(&self.path, e))
+    }
+
+    pub(crate) fn lock(&self) -> Result<MutexGuard<'_, LogFile<St>>, BookingError> {
+        self.file
+            .lock()
+            .map_err(|_| BookingError::new_storage_error(&format!("{}: log lock is poisoned", self.path.display())))
+    }
+
+    /// Appends `records` to the log with a single write and sync, then applies them to the state.
+    ///
+    /// If the write or the sync fails, the log is truncated back to its previous length so a
+    /// partially written record cannot be completed by the next append into a corrupt line.
+    pub(crate) fn append(&self, file: &mut LogFile<St>, records: Vec<St::Record>) -> Result<(), BookingError> {
+        let mut lines = String::new();
+        for record in &records {
+            lines.push_str(&to_line(record)?);
+            lines.push('\n');
+        }
+        let len = file.log.metadata().map_err(|e| io_error(&self.path, e))?.len();
+        if let Err(e) = file.log.write_all(lines.as_bytes()).and_then(|()| file.log.sync_data()) {
+            // The original error is the one worth reporting; a failed rollback leaves an
+            // incomplete last line, which the next `open` drops.
+            let _ = file.log.set_len(len).and_then(|()| file.log.sync_data());
+            return Err(io_error(&self.path, e));
+        }
+        for record in records {
+            file.state.apply(record);
+        }
+        Ok(())
+    }
+}
+
+/// The bookings of a `FileBookingRepository`, also kept by `FileOutboxRepository`.
+#[derive(Debug)]
+pub(crate) struct FileState<T, ID, Timestamp, Metadata, S> {
+    bookings: Vec<Booking<T, ID, Timestamp, Metadata, S>>,
+}
+
+impl<T, ID, Timestamp, Metadata, S> LogState for FileState<T, ID, Timestamp, Metadata, S>
+where
+    T: Serialize + DeserializeOwned,
+    ID: PartialEq + Serialize + DeserializeOwned,
+    Timestamp: Serialize + DeserializeOwned,
+    Metadata: Serialize + DeserializeOwned,
+    S: Serialize + DeserializeOwned,
+{
+    type Record = LogRecord<T, ID, Timestamp, Metadata, S>;
+    type Entry = Booking<T, ID, Timestamp, Metadata, S>;
+
+    fn restore(bookings: Vec<Booking<T, ID, Timestamp, Metadata, S>>) -> Self {
+        Self { bookings }
+    }
+
+    fn apply(&mut self, record: LogRecord<T, ID, Timestamp, Metadata, S>) {
+        match record {
+            LogRecord::Insert { booking } | LogRecord::Update { booking } => {
+                match self.bookings.iter_mut().find(|b| b.id == booking.id) {
+                    Some(stored) => *stored = booking,
+                    None => self.bookings.push(booking),
+                }
+            }
+            LogRecord::Remove { booking_id } => self.bookings.retain(|b| b.id != booking_id),
+        }
+    }
+
+    fn snapshot(&self) -> Result<String, BookingError> {
+        let mut lines = String::new();
+        for booking in &self.bookings {
+            lines.push_str(&to_line(booking)?);
+            lines.push('\n');
+        }
+        Ok(lines)
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S> FileState<T, ID, Timestamp, Metadata, S> {
+    /// Returns the stored bookings.
+    pub(crate) fn bookings(&self) -> &[Booking<T, ID, Timestamp, Metadata, S>] {
+        &self.bookings
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S> FileState<T, ID, Timestamp, Metadata, S>
+where
+    T: Bookable + Clone,
+    ID: PartialEq + Display + Clone,
+    Timestamp: Clone,
+    Metadata: Clone,
+    S: Clone,
+{
+    /// Runs the checks of `BookingRepository::insert_checked` for `bookings`.
+    pub(crate) fn check_insert(
+        &self,
+        bookings: &[Booking<T, ID, Timestamp, Metadata, S>],
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<(), BookingError> {
+        ensure_new_ids(&self.bookings, bookings)?;
+        let related: Vec<_> = self.bookings.iter().filter(|b| is_related(b, bookings)).cloned().collect();
+        check(&related)
+    }
+
+    /// Returns the stored booking `booking` replaces, after running the checks of
+    /// `BookingRepository::update_checked`.
+    pub(crate) fn check_update(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<&Booking<T, ID, Timestamp, Metadata, S>, BookingError> {
+        let Some(stored) = self.bookings.iter().find(|b| b.id == booking.id) else {
+            return Err(BookingError::new_booking_not_found(&booking.id.to_string()));
+        };
+        if booking.version != stored.version + 1 {
+            return Err(BookingError::new_concurrent_modification(
+                &booking.id.to_string(),
+                booking.version,
+                stored.version,
+            ));
+        }
+        let related: Vec<_> = self
+            .bookings
+            .iter()
+            .filter(|b| b.id != booking.id && is_related(b, slice::from_ref(booking)))
+            .cloned()
+            .collect();
+        check(&related)?;
+        Ok(stored)
+    }
+
+    pub(crate) fn get(&self, id: &ID) -> Option<Booking<T, ID, Timestamp, Metadata, S>> {
+        self.bookings.iter().find(|b| &b.id == id).cloned()
+    }
+
+    pub(crate) fn filter<P>(&self, predicate: P) -> Vec<Booking<T, ID, Timestamp, Metadata, S>>
+    where
+        P: Fn(&Booking<T, ID, Timestamp, Metadata, S>) -> bool,
+    {
+        self.bookings.iter().filter(|b| predicate(b)).cloned().collect()
+    }
+}
+
+fn parse_line<V: DeserializeOwned>(path: &Path, index: usize, line: &str) -> Result<V, BookingError> {
+    serde_json::from_str(line)
+        .map_err(|e| BookingError::new_storage_error(&format!("{}: line {}: {}", path.display(), index + 1, e)))
+}
+
+/// Syncs the directory holding `path`, so a file renamed into it survives a crash.
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> Result<(), BookingError> {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    File::open(parent)
+        .and_then(|directory| directory.sync_all())
+        .map_err(|e| io_error(parent, e))
+}
+
+/// Directories cannot be opened as files outside Unix; the rename is left to the file system.
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> Result<(), BookingError> {
+    Ok(())
+}
+
+pub(crate) fn to_line<V: Serialize>(value: &V) -> Result<String, BookingError> {
+    serde_json::to_string(value).map_err(|e| BookingError::new_storage_error(&format!("cannot serialize record: {}", e)))
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> BookingError {
+    BookingError::new_storage_error(&format!("{}: {}", path.display(), error))
+}
+
+impl<T, ID, Timestamp, Metadata, S> BookingRepository<T, ID, Timestamp, Metadata, S>
+    for FileBookingRepository<T, ID, Timestamp, Metadata, S>
+where
+    T: Bookable + Clone + Serialize + DeserializeOwned,
+    ID: PartialEq + Display + Clone + Serialize + DeserializeOwned,
+    Timestamp: Clone + Serialize + DeserializeOwned,
+    Metadata: Clone + Serialize + DeserializeOwned,
+    S: Clone + PartialEq + Serialize + DeserializeOwned,
+{
+    fn insert(&self, booking: Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError> {
+        self.insert_checked(vec![booking], &mut |_| Ok(()))
+    }
+
+    fn insert_checked(
+        &self,
+        bookings: Vec<Booking<T, ID, Timestamp, Metadata, S>>,
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<(), BookingError> {
+        let mut file = self.log.lock()?;
+        file.state.check_insert(&bookings, check)?;
+        let records = bookings.into_iter().map(|booking| LogRecord::Insert { booking }).collect();
+        self.log.append(&mut file, records)
+    }
+
+    fn get(&self, id: &ID) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        Ok(self.log.lock()?.state.get(id))
+    }
+
+    fn update(&self, booking: Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError> {
+        self.update_checked(booking, &mut |_| Ok(()))
+    }
+
+    fn update_checked(
+        &self,
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<(), BookingError> {
+        let mut file = self.log.lock()?;
+        file.state.check_update(&booking, check)?;
+        self.log.append(&mut file, vec![LogRecord::Update { booking }])
+    }
+
+    fn remove(&self, id: &ID) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let mut file = self.log.lock()?;
+        let Some(removed) = file.state.get(id) else {
+            return Ok(None);
+        };
+        self.log.append(&mut file, vec![LogRecord::Remove { booking_id: id.clone() }])?;
+        Ok(Some(removed))
+    }
+
+    fn list_by_user(&self, user_id: &ID) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        Ok(self.log.lock()?.state.filter(|b| b.user_id.as_ref() == Some(user_id)))
+    }
+
+    fn list_by_item(&self, item_id: &str) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        Ok(self.log.lock()?.state.filter(|b| b.item.id() == item_id))
+    }
+
+    fn list_by_status(&self, status: &S) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        Ok(self.log.lock()?.state.filter(|b| &b.status == status))
+    }
+}
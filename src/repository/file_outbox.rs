@@ -0,0 +1,290 @@
+//! File-backed `BookingRepository` storing every booking write together with its event.
+
+use std::fmt::Display;
+use std::path::Path;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use crate::error::BookingError;
+use crate::events::BookingEvent;
+use crate::model::booking::Booking;
+use crate::model::status::{BookingStatus, Status};
+use crate::outbox::{BookingOutbox, OutboxMessage};
+use crate::repository::file::{to_line, FileState, JsonLinesLog, LogRecord, LogState, TruncatedTail};
+use crate::repository::BookingRepository;
+use crate::traits::Bookable;
+
+/// A single line of the outbox log.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum OutboxRecord<T, ID, Timestamp, Metadata, S> {
+    Insert {
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+        message: OutboxMessage<ID, Timestamp, S>,
+    },
+    Update {
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+        message: OutboxMessage<ID, Timestamp, S>,
+    },
+    Remove {
+        booking_id: ID,
+    },
+    Acknowledge {
+        message_id: u64,
+    },
+}
+
+/// A single line of the outbox snapshot file, generic over owned or borrowed bookings and
+/// messages so `compact` can write it without cloning the state.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "entry", rename_all = "snake_case")]
+enum OutboxEntry<B, M> {
+    Booking { booking: B },
+    Message { message: M },
+    Counter { next_message_id: u64 },
+}
+
+#[derive(Debug)]
+struct OutboxState<T, ID, Timestamp, Metadata, S> {
+    index: FileState<T, ID, Timestamp, Metadata, S>,
+    pending: Vec<OutboxMessage<ID, Timestamp, S>>,
+    next_message_id: u64,
+}
+
+impl<T, ID, Timestamp, Metadata, S> OutboxState<T, ID, Timestamp, Metadata, S>
+where
+    ID: PartialEq,
+{
+    /// Queues `message` unless it is already pending, which happens when a log record already part
+    /// of the snapshot is replayed.
+    fn enqueue(&mut self, message: OutboxMessage<ID, Timestamp, S>) {
+        self.next_message_id = self.next_message_id.max(message.id + 1);
+        if !self.pending.iter().any(|m| m.id == message.id) {
+            self.pending.push(message);
+        }
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S> LogState for OutboxState<T, ID, Timestamp, Metadata, S>
+where
+    T: Serialize + DeserializeOwned,
+    ID: PartialEq + Serialize + DeserializeOwned,
+    Timestamp: Serialize + DeserializeOwned,
+    Metadata: Serialize + DeserializeOwned,
+    S: Serialize + DeserializeOwned,
+{
+    type Record = OutboxRecord<T, ID, Timestamp, Metadata, S>;
+    type Entry = OutboxEntry<Booking<T, ID, Timestamp, Metadata, S>, OutboxMessage<ID, Timestamp, S>>;
+
+    fn restore(entries: Vec<Self::Entry>) -> Self {
+        let mut bookings = Vec::new();
+        let mut pending = Vec::new();
+        let mut next_message_id = 1;
+        for entry in entries {
+            match entry {
+                OutboxEntry::Booking { booking } => bookings.push(booking),
+                OutboxEntry::Message { message } => pending.push(message),
+                OutboxEntry::Counter { next_message_id: next } => next_message_id = next_message_id.max(next),
+            }
+        }
+        Self {
+            index: FileState::restore(bookings),
+            pending,
+            next_message_id,
+        }
+    }
+
+    fn apply(&mut self, record: OutboxRecord<T, ID, Timestamp, Metadata, S>) {
+        match record {
+            OutboxRecord::Insert { booking, message } => {
+                self.index.apply(LogRecord::Insert { booking });
+                self.enqueue(message);
+            }
+            OutboxRecord::Update { booking, message } => {
+                self.index.apply(LogRecord::Update { booking });
+                self.enqueue(message);
+            }
+            OutboxRecord::Remove { booking_id } => {
+                self.pending.retain(|m| m.event.booking_id() != &booking_id);
+                self.index.apply(LogRecord::Remove { booking_id });
+            }
+            OutboxRecord::Acknowledge { message_id } => {
+                self.pending.retain(|m| m.id != message_id);
+            }
+        }
+    }
+
+    fn snapshot(&self) -> Result<String, BookingError> {
+        let mut entries = Vec::new();
+        entries.push(OutboxEntry::Counter { next_message_id: self.next_message_id });
+        entries.extend(self.index.bookings().iter().map(|booking| OutboxEntry::Booking { booking }));
+        entries.extend(self.pending.iter().map(|message| OutboxEntry::Message { message }));
+
+        let mut lines = String::new();
+        for entry in &entries {
+            lines.push_str(&to_line(entry)?);
+            lines.push('\n');
+        }
+        Ok(lines)
+    }
+}
+
+/// A `BookingRepository` and `BookingOutbox` persisted to an append-only JSON-lines log.
+///
+/// Every insert and update appends one line holding both the booking and the `BookingEvent` it
+/// produced (`Created` for an insert, the event of its latest status change for an update), so a
+/// write and its event are stored together or not at all. Removing a booking, which only happens
+/// to roll back a booking that could not be waitlisted, also drops its undelivered events.
+/// Delivered events are recorded with `acknowledge`.
+///
+/// The log is kept like the one of `file::FileBookingRepository`: a line whose write fails is
+/// truncated away again, `open` replays the snapshot file and then the log, dropping an
+/// incomplete last line left by a crash (see `truncated_tail`), and `compact` rewrites the
+/// bookings, the undelivered events and the next message ID into the snapshot file and empties
+/// the log.
+///
+/// Intended for local testing and small deployments; drain it with `outbox::OutboxRelay`.
+#[derive(Debug)]
+pub struct FileOutboxRepository<T, ID, Timestamp, Metadata, S = BookingStatus> {
+    log: JsonLinesLog<OutboxState<T, ID, Timestamp, Metadata, S>>,
+}
+
+impl<T, ID, Timestamp, Metadata, S> FileOutboxRepository<T, ID, Timestamp, Metadata, S>
+where
+    T: Serialize + DeserializeOwned,
+    ID: PartialEq + Serialize + DeserializeOwned,
+    Timestamp: Serialize + DeserializeOwned,
+    Metadata: Serialize + DeserializeOwned,
+    S: Serialize + DeserializeOwned,
+{
+    /// Opens the outbox log at `path`, creating it if it does not exist, and rebuilds the
+    /// bookings and undelivered events from the snapshot file next to it and the log.
+    ///
+    /// Returns `BookingError::StorageError` if a file cannot be read or a complete line cannot be
+    /// parsed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BookingError> {
+        Ok(Self { log: JsonLinesLog::open(path.as_ref())? })
+    }
+
+    /// Returns the path of the outbox log.
+    pub fn path(&self) -> &Path {
+        self.log.path()
+    }
+
+    /// Returns the path of the snapshot file: the log path with `.snapshot` appended.
+    pub fn snapshot_path(&self) -> &Path {
+        self.log.snapshot_path()
+    }
+
+    /// Returns the incomplete record dropped from the end of the log on open, if any.
+    pub fn truncated_tail(&self) -> Option<&TruncatedTail> {
+        self.log.truncated_tail()
+    }
+
+    /// Rewrites the current bookings and undelivered events into the snapshot file and empties
+    /// the log. Message IDs keep increasing across compactions.
+    pub fn compact(&self) -> Result<(), BookingError> {
+        self.log.compact()
+    }
+
+    /// Returns the size of the log in bytes, e.g. to decide when to `compact`.
+    pub fn log_len(&self) -> Result<u64, BookingError> {
+        self.log.log_len()
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S> BookingRepository<T, ID, Timestamp, Metadata, S>
+    for FileOutboxRepository<T, ID, Timestamp, Metadata, S>
+where
+    T: Bookable + Clone + Serialize + DeserializeOwned,
+    ID: PartialEq + Display + Clone + Serialize + DeserializeOwned,
+    Timestamp: Clone + Serialize + DeserializeOwned,
+    Metadata: Clone + Serialize + DeserializeOwned,
+    S: Status + Serialize + DeserializeOwned,
+{
+    fn insert(&self, booking: Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError> {
+        self.insert_checked(vec![booking], &mut |_| Ok(()))
+    }
+
+    fn insert_checked(
+        &self,
+        bookings: Vec<Booking<T, ID, Timestamp, Metadata, S>>,
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<(), BookingError> {
+        let mut file = self.log.lock()?;
+        file.state.index.check_insert(&bookings, check)?;
+        let first_message_id = file.state.next_message_id;
+        let records = bookings
+            .into_iter()
+            .zip(first_message_id..)
+            .map(|(booking, id)| {
+                let message = OutboxMessage { id, event: BookingEvent::created(&booking) };
+                OutboxRecord::Insert { booking, message }
+            })
+            .collect();
+        self.log.append(&mut file, records)
+    }
+
+    fn get(&self, id: &ID) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        Ok(self.log.lock()?.state.index.get(id))
+    }
+
+    fn update(&self, booking: Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError> {
+        self.update_checked(booking, &mut |_| Ok(()))
+    }
+
+    fn update_checked(
+        &self,
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<(), BookingError> {
+        let mut file = self.log.lock()?;
+        let previous = file.state.index.check_update(&booking, check)?;
+        let message = OutboxMessage {
+            id: file.state.next_message_id,
+            event: BookingEvent::updated(previous, &booking),
+        };
+        self.log.append(&mut file, vec![OutboxRecord::Update { booking, message }])
+    }
+
+    fn remove(&self, id: &ID) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let mut file = self.log.lock()?;
+        let Some(removed) = file.state.index.get(id) else {
+            return Ok(None);
+        };
+        self.log.append(&mut file, vec![OutboxRecord::Remove { booking_id: id.clone() }])?;
+        Ok(Some(removed))
+    }
+
+    fn list_by_user(&self, user_id: &ID) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        Ok(self.log.lock()?.state.index.filter(|b| b.user_id.as_ref() == Some(user_id)))
+    }
+
+    fn list_by_item(&self, item_id: &str) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        Ok(self.log.lock()?.state.index.filter(|b| b.item.id() == item_id))
+    }
+
+    fn list_by_status(&self, status: &S) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        Ok(self.log.lock()?.state.index.filter(|b| &b.status == status))
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S> BookingOutbox<ID, Timestamp, S> for FileOutboxRepository<T, ID, Timestamp, Metadata, S>
+where
+    T: Serialize + DeserializeOwned,
+    ID: PartialEq + Clone + Serialize + DeserializeOwned,
+    Timestamp: Clone + Serialize + DeserializeOwned,
+    Metadata: Serialize + DeserializeOwned,
+    S: Clone + Serialize + DeserializeOwned,
+{
+    fn pending_messages(&self, limit: usize) -> Result<Vec<OutboxMessage<ID, Timestamp, S>>, BookingError> {
+        Ok(self.log.lock()?.state.pending.iter().take(limit).cloned().collect())
+    }
+
+    fn acknowledge(&self, message_id: u64) -> Result<(), BookingError> {
+        let mut file = self.log.lock()?;
+        if !file.state.pending.iter().any(|m| m.id == message_id) {
+            return Ok(());
+        }
+        self.log.append(&mut file, vec![OutboxRecord::Acknowledge { message_id }])
+    }
+}
@@ -0,0 +1,153 @@
+//! Thread-safe in-memory implementation of `BookingRepository`.
+
+use std::fmt::Display;
+use std::slice;
+use std::sync::RwLock;
+use crate::error::BookingError;
+use crate::model::booking::Booking;
+use crate::model::status::BookingStatus;
+use crate::repository::{ensure_new_ids, is_related, BookingRepository};
+use crate::traits::Bookable;
+
+/// An in-memory `BookingRepository` backed by a `RwLock`.
+///
+/// Bookings are kept in insertion order, so listings are deterministic.
+/// Useful for tests, prototypes, and single-process applications.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::manager::BookingManager;
+/// use booking_kit::repository::memory::InMemoryBookingRepository;
+/// use booking_kit::traits::Bookable;
+///
+/// #[derive(Debug, Clone)]
+/// struct Room { id: &'static str }
+/// impl Bookable for Room {
+///     fn id(&self) -> &str { self.id }
+///     fn is_available(&self) -> bool { true }
+/// }
+///
+/// let manager = BookingManager::new(InMemoryBookingRepository::new());
+/// manager.create_booking("booking-001", None, Room { id: "R101" }, 0, None, None::<()>).unwrap();
+///
+/// let confirmed = manager.confirm_booking(&"booking-001").unwrap();
+/// assert!(confirmed.is_confirmed());
+/// ```
+#[derive(Debug)]
+pub struct InMemoryBookingRepository<T, ID, Timestamp, Metadata, S = BookingStatus> {
+    bookings: RwLock<Vec<Booking<T, ID, Timestamp, Metadata, S>>>,
+}
+
+impl<T, ID, Timestamp, Metadata, S> InMemoryBookingRepository<T, ID, Timestamp, Metadata, S> {
+    /// Creates an empty repository.
+    pub fn new() -> Self {
+        Self { bookings: RwLock::new(Vec::new()) }
+    }
+
+    /// Returns the number of stored bookings.
+    pub fn len(&self) -> usize {
+        self.bookings.read().map(|bookings| bookings.len()).unwrap_or(0)
+    }
+
+    /// Returns `true` if no bookings are stored.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S> Default for InMemoryBookingRepository<T, ID, Timestamp, Metadata, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn poisoned<E>(_: E) -> BookingError {
+    BookingError::new_storage_error("in-memory repository lock is poisoned")
+}
+
+impl<T, ID, Timestamp, Metadata, S> BookingRepository<T, ID, Timestamp, Metadata, S>
+    for InMemoryBookingRepository<T, ID, Timestamp, Metadata, S>
+where
+    T: Bookable + Clone,
+    ID: PartialEq + Display + Clone,
+    Timestamp: Clone,
+    Metadata: Clone,
+    S: Clone + PartialEq,
+{
+    fn insert(&self, booking: Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError> {
+        let mut bookings = self.bookings.write().map_err(poisoned)?;
+        if bookings.iter().any(|b| b.id == booking.id) {
+            return Err(BookingError::new_duplicate_booking(&booking.id.to_string()));
+        }
+        bookings.push(booking);
+        Ok(())
+    }
+
+    fn insert_checked(
+        &self,
+        new_bookings: Vec<Booking<T, ID, Timestamp, Metadata, S>>,
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<(), BookingError> {
+        let mut bookings = self.bookings.write().map_err(poisoned)?;
+        ensure_new_ids(&bookings, &new_bookings)?;
+        let related: Vec<_> = bookings.iter().filter(|b| is_related(b, &new_bookings)).cloned().collect();
+        check(&related)?;
+        bookings.extend(new_bookings);
+        Ok(())
+    }
+
+    fn get(&self, id: &ID) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let bookings = self.bookings.read().map_err(poisoned)?;
+        Ok(bookings.iter().find(|b| &b.id == id).cloned())
+    }
+
+    fn update(&self, booking: Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError> {
+        self.update_checked(booking, &mut |_| Ok(()))
+    }
+
+    fn update_checked(
+        &self,
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<(), BookingError> {
+        let mut bookings = self.bookings.write().map_err(poisoned)?;
+        let Some(index) = bookings.iter().position(|b| b.id == booking.id) else {
+            return Err(BookingError::new_booking_not_found(&booking.id.to_string()));
+        };
+        if booking.version != bookings[index].version + 1 {
+            return Err(BookingError::new_concurrent_modification(
+                &booking.id.to_string(),
+                booking.version,
+                bookings[index].version,
+            ));
+        }
+        let related: Vec<_> = bookings
+            .iter()
+            .filter(|b| b.id != booking.id && is_related(b, slice::from_ref(&booking)))
+            .cloned()
+            .collect();
+        check(&related)?;
+        bookings[index] = booking;
+        Ok(())
+    }
+
+    fn remove(&self, id: &ID) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let mut bookings = self.bookings.write().map_err(poisoned)?;
+        Ok(bookings.iter().position(|b| &b.id == id).map(|index| bookings.remove(index)))
+    }
+
+    fn list_by_user(&self, user_id: &ID) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let bookings = self.bookings.read().map_err(poisoned)?;
+        Ok(bookings.iter().filter(|b| b.user_id.as_ref() == Some(user_id)).cloned().collect())
+    }
+
+    fn list_by_item(&self, item_id: &str) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let bookings = self.bookings.read().map_err(poisoned)?;
+        Ok(bookings.iter().filter(|b| b.item.id() == item_id).cloned().collect())
+    }
+
+    fn list_by_status(&self, status: &S) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let bookings = self.bookings.read().map_err(poisoned)?;
+        Ok(bookings.iter().filter(|b| &b.status == status).cloned().collect())
+    }
+}
@@ -0,0 +1,127 @@
+//! Storage abstraction for persisting and querying bookings.
+
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::fmt::Display;
+use crate::error::BookingError;
+use crate::model::booking::Booking;
+use crate::model::status::BookingStatus;
+#[cfg(feature = "std")]
+use crate::traits::Bookable;
+
+#[cfg(feature = "std")]
+pub mod memory;
+#[cfg(all(feature = "std", feature = "json"))]
+pub mod file;
+#[cfg(all(feature = "std", feature = "json"))]
+pub mod file_outbox;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+/// The `BookingRepository` trait defines how bookings are stored and retrieved.
+///
+/// Implementations are free to use any backend (in-memory, database, files, ...).
+/// All methods take `&self` so a repository can be shared between threads; implementations
+/// are expected to handle their own synchronization.
+///
+/// `S` is the status type of the stored bookings; see `Status`.
+pub trait BookingRepository<T, ID, Timestamp, Metadata, S = BookingStatus> {
+    /// Stores a new booking.
+    ///
+    /// Returns `BookingError::DuplicateBooking` if a booking with the same ID already exists.
+    fn insert(&self, booking: Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError>;
+
+    /// Stores new bookings after `check` accepted them, as one atomic step.
+    ///
+    /// `check` receives every stored booking sharing an item or a user with one of `bookings`,
+    /// read while the repository is locked (or inside the transaction) that also stores them, so
+    /// no other writer can change the outcome in between. Either all bookings are stored or none.
+    /// This is what makes capacity and conflict checks hold under concurrent inserts.
+    ///
+    /// Returns the error of `check` if it rejects the bookings, and
+    /// `BookingError::DuplicateBooking` if an ID is already stored or used twice in `bookings`.
+    fn insert_checked(
+        &self,
+        bookings: Vec<Booking<T, ID, Timestamp, Metadata, S>>,
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<(), BookingError>;
+
+    /// Returns the booking with the given ID, or `None` if it does not exist.
+    fn get(&self, id: &ID) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError>;
+
+    /// Replaces a stored booking with the given one, matched by ID.
+    ///
+    /// The given booking must carry the stored `version` plus one, i.e. it was loaded at the
+    /// current version and then changed once. This makes concurrent read-modify-write cycles
+    /// safe: the slower writer is rejected instead of overwriting the faster one.
+    ///
+    /// Returns `BookingError::BookingNotFound` if no booking with that ID exists, and
+    /// `BookingError::ConcurrentModification` if the versions do not match.
+    fn update(&self, booking: Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError>;
+
+    /// Replaces a stored booking like `update`, after `check` accepted it, as one atomic step.
+    ///
+    /// `check` receives every other stored booking sharing the item or the user of `booking`,
+    /// read while the repository is locked (or inside the transaction) that also stores it, as
+    /// for `insert_checked`. This is what makes capacity and conflict checks hold when a booking
+    /// starts holding its item again, e.g. when it is promoted from a waitlist.
+    ///
+    /// Returns the same errors as `update`, checked before `check` runs, and the error of `check`
+    /// if it rejects the booking.
+    fn update_checked(
+        &self,
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<(), BookingError>;
+
+    /// Removes the booking with the given ID and returns it, or `None` if it does not exist.
+    ///
+    /// Used to roll back a stored booking whose follow-up step failed, e.g. queuing it on a waitlist.
+    fn remove(&self, id: &ID) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError>;
+
+    /// Returns all bookings made by the given user.
+    fn list_by_user(&self, user_id: &ID) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError>;
+
+    /// Returns all bookings for the item with the given `Bookable::id()`.
+    fn list_by_item(&self, item_id: &str) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError>;
+
+    /// Returns all bookings currently in the given status.
+    fn list_by_status(&self, status: &S) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError>;
+}
+
+/// Returns `true` if `stored` shares its item or its user with one of `bookings`, i.e. it is
+/// passed to the check of `BookingRepository::insert_checked`, or of `update_checked` unless it is
+/// the updated booking itself.
+#[cfg(feature = "std")]
+pub(crate) fn is_related<T, ID, Timestamp, Metadata, S>(
+    stored: &Booking<T, ID, Timestamp, Metadata, S>,
+    bookings: &[Booking<T, ID, Timestamp, Metadata, S>],
+) -> bool
+where
+    T: Bookable,
+    ID: PartialEq,
+{
+    bookings.iter().any(|booking| {
+        booking.item.id() == stored.item.id() || (booking.user_id.is_some() && booking.user_id == stored.user_id)
+    })
+}
+
+/// Returns `BookingError::DuplicateBooking` for the first of `bookings` whose ID is used by a
+/// booking before it or by one of `stored`.
+#[cfg(feature = "std")]
+pub(crate) fn ensure_new_ids<T, ID, Timestamp, Metadata, S>(
+    stored: &[Booking<T, ID, Timestamp, Metadata, S>],
+    bookings: &[Booking<T, ID, Timestamp, Metadata, S>],
+) -> Result<(), BookingError>
+where
+    ID: PartialEq + Display,
+{
+    for (index, booking) in bookings.iter().enumerate() {
+        if stored.iter().chain(&bookings[..index]).any(|other| other.id == booking.id) {
+            return Err(BookingError::new_duplicate_booking(&booking.id.to_string()));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,485 @@
+//! SQLite-backed implementation of `BookingRepository`, available with the `sqlite` feature.
+
+use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
+use std::path::Path;
+use std::slice;
+use std::sync::{Mutex, MutexGuard};
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension, TransactionBehavior};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::error::BookingError;
+use crate::model::booking::Booking;
+use crate::model::history::ChangeContext;
+use crate::model::period::BookingPeriod;
+use crate::model::status::{BookingStatus, Status};
+use crate::repository::BookingRepository;
+use crate::traits::Bookable;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS bookings (
+        id           TEXT PRIMARY KEY NOT NULL,
+        user_id      TEXT,
+        item_id      TEXT NOT NULL,
+        item         TEXT NOT NULL,
+        quantity     INTEGER NOT NULL,
+        status       TEXT NOT NULL,
+        created_at   TEXT NOT NULL,
+        expires_at   TEXT,
+        period_start TEXT,
+        period_end   TEXT,
+        metadata     TEXT,
+        history      TEXT NOT NULL,
+        version      INTEGER NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS bookings_user_id ON bookings (user_id);
+    CREATE INDEX IF NOT EXISTS bookings_item_id ON bookings (item_id);
+    CREATE INDEX IF NOT EXISTS bookings_status ON bookings (status);
+";
+
+const COLUMNS: &str = "id, user_id, item_id, item, quantity, status, created_at, expires_at, \
+                       period_start, period_end, metadata, history, version";
+
+/// A `BookingRepository` persisted to an SQLite database.
+///
+/// Each booking is one row of the `bookings` table, created on open if it does not exist. Scalar
+/// fields are stored as columns, the status by name, and the generic fields (`id`, `user_id`,
+/// `item`, timestamps, `metadata`, `history`) as JSON through their serde implementations. The
+/// table is indexed by user ID, item ID and status, so `list_by_user`, `list_by_item` and
+/// `list_by_status` do not scan it. Listings are returned in insertion order.
+///
+/// `update` enforces the `version` contract of `BookingRepository`, and `insert_checked` runs its
+/// check, inside an immediate transaction, so both also hold between processes sharing the
+/// database file.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::manager::BookingManager;
+/// use booking_kit::repository::sqlite::SqliteBookingRepository;
+/// use booking_kit::traits::Bookable;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Clone, Serialize, Deserialize)]
+/// struct Room { id: String }
+/// impl Bookable for Room {
+///     fn id(&self) -> &str { &self.id }
+///     fn is_available(&self) -> bool { true }
+/// }
+///
+/// let manager = BookingManager::new(SqliteBookingRepository::open_in_memory().unwrap());
+/// manager.create_booking(1, None, Room { id: "R101".into() }, 0_i64, None, None::<()>).unwrap();
+///
+/// assert!(manager.confirm_booking(&1).unwrap().is_confirmed());
+/// ```
+#[derive(Debug)]
+pub struct SqliteBookingRepository<T, ID, Timestamp, Metadata, S = BookingStatus> {
+    connection: Mutex<Connection>,
+    marker: PhantomData<Booking<T, ID, Timestamp, Metadata, S>>,
+}
+
+impl<T, ID, Timestamp, Metadata, S> SqliteBookingRepository<T, ID, Timestamp, Metadata, S> {
+    /// Opens the database at `path`, creating it and the schema if needed.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, BookingError> {
+        Self::from_connection(Connection::open(path).map_err(sql_error)?)
+    }
+
+    /// Opens a private in-memory database, dropped with the repository.
+    pub fn open_in_memory() -> Result<Self, BookingError> {
+        Self::from_connection(Connection::open_in_memory().map_err(sql_error)?)
+    }
+
+    /// Uses an existing connection, creating the schema if needed.
+    pub fn from_connection(connection: Connection) -> Result<Self, BookingError> {
+        connection.execute_batch(SCHEMA).map_err(sql_error)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+            marker: PhantomData,
+        })
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, Connection>, BookingError> {
+        self.connection
+            .lock()
+            .map_err(|_| BookingError::new_storage_error("sqlite repository lock is poisoned"))
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S> SqliteBookingRepository<T, ID, Timestamp, Metadata, S>
+where
+    T: Bookable + Serialize + DeserializeOwned,
+    ID: Display + Serialize + DeserializeOwned,
+    Timestamp: Serialize + DeserializeOwned,
+    Metadata: Serialize + DeserializeOwned,
+    S: Status + Serialize + DeserializeOwned,
+{
+    /// Moves a stored booking to the `to` status within a single transaction, and returns it.
+    ///
+    /// The booking is read, checked with `Status::can_transition_to`, and written back with
+    /// the change described by `context` recorded in its history and its `version` incremented,
+    /// while the database is locked for writing. A concurrent writer, even in another process,
+    /// therefore cannot make the check stale. Unlike `BookingManager::transition_booking`, neither
+    /// a `TransitionPolicy` nor booking rules are evaluated, and no event is emitted.
+    ///
+    /// Returns `BookingError::BookingNotFound` if the booking does not exist, and
+    /// `BookingError::InvalidStatusTransition` if the transition is not allowed.
+    pub fn transition(
+        &self,
+        id: &ID,
+        to: S,
+        context: ChangeContext<ID, Timestamp>,
+    ) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError> {
+        let mut connection = self.lock()?;
+        let transaction = connection
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(sql_error)?;
+
+        let mut booking: Booking<T, ID, Timestamp, Metadata, S> = select_one(&transaction, id)?
+            .ok_or_else(|| BookingError::new_booking_not_found(&id.to_string()))?;
+        if !booking.status.can_transition_to(&to) {
+            return Err(booking.status.transition_error(&to));
+        }
+        let from = std::mem::replace(&mut booking.status, to.clone());
+        booking.history.push(context.into_change(from, to));
+        booking.version += 1;
+
+        write(&transaction, Statement::Update, &booking)?;
+        transaction.commit().map_err(sql_error)?;
+        Ok(booking)
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S> BookingRepository<T, ID, Timestamp, Metadata, S>
+    for SqliteBookingRepository<T, ID, Timestamp, Metadata, S>
+where
+    T: Bookable + Serialize + DeserializeOwned,
+    ID: Display + Serialize + DeserializeOwned,
+    Timestamp: Serialize + DeserializeOwned,
+    Metadata: Serialize + DeserializeOwned,
+    S: Serialize + DeserializeOwned + Debug,
+{
+    fn insert(&self, booking: Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError> {
+        let mut connection = self.lock()?;
+        let transaction = connection
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(sql_error)?;
+        let exists = transaction
+            .query_row("SELECT 1 FROM bookings WHERE id = ?1", [to_json(&booking.id)?], |_| Ok(()))
+            .optional()
+            .map_err(sql_error)?;
+        if exists.is_some() {
+            return Err(BookingError::new_duplicate_booking(&booking.id.to_string()));
+        }
+
+        write(&transaction, Statement::Insert, &booking)?;
+        transaction.commit().map_err(sql_error)
+    }
+
+    fn insert_checked(
+        &self,
+        bookings: Vec<Booking<T, ID, Timestamp, Metadata, S>>,
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<(), BookingError> {
+        let mut connection = self.lock()?;
+        let transaction = connection
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(sql_error)?;
+
+        let ids = bookings.iter().map(|booking| to_json(&booking.id)).collect::<Result<Vec<_>, _>>()?;
+        for (index, id) in ids.iter().enumerate() {
+            let exists = transaction
+                .query_row("SELECT 1 FROM bookings WHERE id = ?1", [id], |_| Ok(()))
+                .optional()
+                .map_err(sql_error)?;
+            if exists.is_some() || ids[..index].contains(id) {
+                return Err(BookingError::new_duplicate_booking(&bookings[index].id.to_string()));
+            }
+        }
+
+        check(&select_related(&transaction, &bookings)?)?;
+        for booking in &bookings {
+            write(&transaction, Statement::Insert, booking)?;
+        }
+        transaction.commit().map_err(sql_error)
+    }
+
+    fn get(&self, id: &ID) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let connection = self.lock()?;
+        select_one(&connection, id)
+    }
+
+    fn update(&self, booking: Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError> {
+        self.update_checked(booking, &mut |_| Ok(()))
+    }
+
+    fn update_checked(
+        &self,
+        booking: Booking<T, ID, Timestamp, Metadata, S>,
+        check: &mut dyn FnMut(&[Booking<T, ID, Timestamp, Metadata, S>]) -> Result<(), BookingError>,
+    ) -> Result<(), BookingError> {
+        let mut connection = self.lock()?;
+        let transaction = connection
+            .transaction_with_behavior(TransactionBehavior::Immediate)
+            .map_err(sql_error)?;
+
+        let stored: Option<i64> = transaction
+            .query_row("SELECT version FROM bookings WHERE id = ?1", [to_json(&booking.id)?], |row| row.get(0))
+            .optional()
+            .map_err(sql_error)?;
+        let Some(stored) = stored else {
+            return Err(BookingError::new_booking_not_found(&booking.id.to_string()));
+        };
+        let stored = stored as u64;
+        if booking.version != stored + 1 {
+            return Err(BookingError::new_concurrent_modification(&booking.id.to_string(), booking.version, stored));
+        }
+
+        let id = to_json(&booking.id)?;
+        let mut related = Vec::new();
+        for other in select_related(&transaction, slice::from_ref(&booking))? {
+            if to_json(&other.id)? != id {
+                related.push(other);
+            }
+        }
+        check(&related)?;
+        write(&transaction, Statement::Update, &booking)?;
+        transaction.commit().map_err(sql_error)
+    }
+
+    fn remove(&self, id: &ID) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let mut connection = self.lock()?;
+        let transaction = connection.transaction().map_err(sql_error)?;
+        let removed = select_one(&transaction, id)?;
+        if removed.is_some() {
+            transaction
+                .execute("DELETE FROM bookings WHERE id = ?1", [to_json(id)?])
+                .map_err(sql_error)?;
+        }
+        transaction.commit().map_err(sql_error)?;
+        Ok(removed)
+    }
+
+    fn list_by_user(&self, user_id: &ID) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let connection = self.lock()?;
+        select_where(&connection, "user_id", to_json(user_id)?)
+    }
+
+    fn list_by_item(&self, item_id: &str) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let connection = self.lock()?;
+        select_where(&connection, "item_id", item_id.to_string())
+    }
+
+    fn list_by_status(&self, status: &S) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError> {
+        let connection = self.lock()?;
+        select_where(&connection, "status", status_name(status)?)
+    }
+}
+
+/// A row of the `bookings` table before its JSON columns are decoded.
+struct Row {
+    id: String,
+    user_id: Option<String>,
+    item: String,
+    quantity: i64,
+    status: String,
+    created_at: String,
+    expires_at: Option<String>,
+    period_start: Option<String>,
+    period_end: Option<String>,
+    metadata: Option<String>,
+    history: String,
+    version: i64,
+}
+
+impl Row {
+    fn read(row: &rusqlite::Row<'_>) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            user_id: row.get(1)?,
+            item: row.get(3)?,
+            quantity: row.get(4)?,
+            status: row.get(5)?,
+            created_at: row.get(6)?,
+            expires_at: row.get(7)?,
+            period_start: row.get(8)?,
+            period_end: row.get(9)?,
+            metadata: row.get(10)?,
+            history: row.get(11)?,
+            version: row.get(12)?,
+        })
+    }
+
+    fn decode<T, ID, Timestamp, Metadata, S>(self) -> Result<Booking<T, ID, Timestamp, Metadata, S>, BookingError>
+    where
+        T: DeserializeOwned,
+        ID: DeserializeOwned,
+        Timestamp: DeserializeOwned,
+        Metadata: DeserializeOwned,
+        S: DeserializeOwned,
+    {
+        let period = match (self.period_start, self.period_end) {
+            (Some(start), Some(end)) => Some(BookingPeriod::new(from_json(&start)?, from_json(&end)?)),
+            _ => None,
+        };
+
+        Ok(Booking {
+            id: from_json(&self.id)?,
+            user_id: self.user_id.as_deref().map(from_json).transpose()?,
+            item: from_json(&self.item)?,
+            quantity: u32::try_from(self.quantity)
+                .map_err(|_| BookingError::new_storage_error(&format!("invalid quantity {}", self.quantity)))?,
+            status: serde_json::from_value(serde_json::Value::String(self.status))
+                .map_err(|e| BookingError::new_storage_error(&format!("invalid status: {}", e)))?,
+            created_at: from_json(&self.created_at)?,
+            expires_at: self.expires_at.as_deref().map(from_json).transpose()?,
+            period,
+            metadata: self.metadata.as_deref().map(from_json).transpose()?,
+            history: from_json(&self.history)?,
+            version: self.version as u64,
+        })
+    }
+}
+
+/// How `write` stores a booking.
+enum Statement {
+    /// Adds a new row.
+    Insert,
+    /// Replaces the row with the booking's ID.
+    Update,
+}
+
+fn write<T, ID, Timestamp, Metadata, S>(
+    connection: &Connection,
+    statement: Statement,
+    booking: &Booking<T, ID, Timestamp, Metadata, S>,
+) -> Result<(), BookingError>
+where
+    T: Bookable + Serialize,
+    ID: Serialize,
+    Timestamp: Serialize,
+    Metadata: Serialize,
+    S: Serialize + Debug,
+{
+    let sql = match statement {
+        Statement::Insert => format!("INSERT INTO bookings ({COLUMNS}) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)"),
+        Statement::Update => "UPDATE bookings SET user_id = ?2, item_id = ?3, item = ?4, quantity = ?5, status = ?6, \
+              created_at = ?7, expires_at = ?8, period_start = ?9, period_end = ?10, metadata = ?11, \
+              history = ?12, version = ?13 WHERE id = ?1"
+            .to_string(),
+    };
+
+    connection
+        .execute(
+            &sql,
+            params![
+                to_json(&booking.id)?,
+                booking.user_id.as_ref().map(to_json).transpose()?,
+                booking.item.id(),
+                to_json(&booking.item)?,
+                i64::from(booking.quantity),
+                status_name(&booking.status)?,
+                to_json(&booking.created_at)?,
+                booking.expires_at.as_ref().map(to_json).transpose()?,
+                booking.period.as_ref().map(|p| to_json(&p.start)).transpose()?,
+                booking.period.as_ref().map(|p| to_json(&p.end)).transpose()?,
+                booking.metadata.as_ref().map(to_json).transpose()?,
+                to_json(&booking.history)?,
+                booking.version as i64,
+            ],
+        )
+        .map_err(sql_error)?;
+    Ok(())
+}
+
+fn select_one<T, ID, Timestamp, Metadata, S>(
+    connection: &Connection,
+    id: &ID,
+) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError>
+where
+    T: DeserializeOwned,
+    ID: Serialize + DeserializeOwned,
+    Timestamp: DeserializeOwned,
+    Metadata: DeserializeOwned,
+    S: DeserializeOwned,
+{
+    Ok(select_where(connection, "id", to_json(id)?)?.pop())
+}
+
+/// Returns the bookings whose `column` equals `value`, in insertion order.
+fn select_where<T, ID, Timestamp, Metadata, S>(
+    connection: &Connection,
+    column: &str,
+    value: String,
+) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError>
+where
+    T: DeserializeOwned,
+    ID: DeserializeOwned,
+    Timestamp: DeserializeOwned,
+    Metadata: DeserializeOwned,
+    S: DeserializeOwned,
+{
+    let mut statement = connection
+        .prepare_cached(&format!("SELECT {COLUMNS} FROM bookings WHERE {column} = ?1 ORDER BY rowid"))
+        .map_err(sql_error)?;
+    let rows = statement
+        .query_map([value], Row::read)
+        .map_err(sql_error)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(sql_error)?;
+    rows.into_iter().map(Row::decode).collect()
+}
+
+/// Returns the bookings sharing an item or a user with one of `bookings`, in insertion order.
+fn select_related<T, ID, Timestamp, Metadata, S>(
+    connection: &Connection,
+    bookings: &[Booking<T, ID, Timestamp, Metadata, S>],
+) -> Result<Vec<Booking<T, ID, Timestamp, Metadata, S>>, BookingError>
+where
+    T: Bookable + DeserializeOwned,
+    ID: Serialize + DeserializeOwned,
+    Timestamp: DeserializeOwned,
+    Metadata: DeserializeOwned,
+    S: DeserializeOwned,
+{
+    let mut conditions = Vec::new();
+    let mut values = Vec::new();
+    for booking in bookings {
+        conditions.push("item_id = ?");
+        values.push(booking.item.id().to_string());
+        if let Some(user_id) = &booking.user_id {
+            conditions.push("user_id = ?");
+            values.push(to_json(user_id)?);
+        }
+    }
+    if conditions.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut statement = connection
+        .prepare(&format!("SELECT {COLUMNS} FROM bookings WHERE {} ORDER BY rowid", conditions.join(" OR ")))
+        .map_err(sql_error)?;
+    let rows = statement
+        .query_map(params_from_iter(values), Row::read)
+        .map_err(sql_error)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(sql_error)?;
+    rows.into_iter().map(Row::decode).collect()
+}
+
+fn status_name<S: Serialize + Debug>(status: &S) -> Result<String, BookingError> {
+    match serde_json::to_value(status) {
+        Ok(serde_json::Value::String(name)) => Ok(name),
+        _ => Err(BookingError::new_storage_error(&format!("cannot store status {:?}", status))),
+    }
+}
+
+fn to_json<V: Serialize + ?Sized>(value: &V) -> Result<String, BookingError> {
+    serde_json::to_string(value).map_err(|e| BookingError::new_storage_error(&format!("cannot serialize column: {}", e)))
+}
+
+fn from_json<V: DeserializeOwned>(json: &str) -> Result<V, BookingError> {
+    serde_json::from_str(json).map_err(|e| BookingError::new_storage_error(&format!("cannot deserialize column: {}", e)))
+}
+
+fn sql_error(error: rusqlite::Error) -> BookingError {
+    BookingError::new_storage_error(&format!("sqlite: {}", error))
+}
@@ -0,0 +1,449 @@
+//! Composable booking rules evaluated by `BookingManager` on creation and on transitions.
+//!
+//! A rule receives the candidate `Booking` and a `RuleContext`, and returns a structured
+//! `RuleFailure` when the booking is not acceptable. Rules are combined with `All`, `Any`
+//! and `Not`, and installed on a manager with `BookingManager::with_rules`.
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::error::BookingError;
+use crate::model::booking::Booking;
+use crate::model::period::BookingPeriod;
+use crate::model::status::{BookingStatus, Status};
+use crate::policy::Transition;
+use crate::timestamp::BookingTime;
+use crate::traits::Bookable;
+
+/// Describes why a booking rule rejected a booking.
+///
+/// # Fields
+/// - `code`: Machine-readable identifier of the rule, e.g. `"min_lead_time"`.
+/// - `message`: Human-readable explanation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RuleFailure {
+    pub code: String,
+    pub message: String,
+}
+
+impl RuleFailure {
+    /// Creates a failure with the given rule code and message.
+    pub fn new(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            code: code.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for RuleFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl From<RuleFailure> for BookingError {
+    fn from(failure: RuleFailure) -> Self {
+        BookingError::new_rule_validation_failed(&failure.to_string())
+    }
+}
+
+/// Information available to a rule besides the booking itself.
+///
+/// # Fields
+/// - `now`: The current time; the booking's `created_at` on creation, and the timestamp
+///   of the `ChangeContext` on transitions (if any).
+/// - `transition`: `None` when a booking is being created, or the status change being attempted.
+/// - `user_bookings`: Other bookings of the same user known to the manager.
+/// - `item_bookings`: Other bookings of the same item known to the manager.
+#[derive(Debug)]
+pub struct RuleContext<'a, T, ID, Timestamp, Metadata, S = BookingStatus> {
+    pub now: Option<&'a Timestamp>,
+    pub transition: Option<Transition<S>>,
+    pub user_bookings: &'a [Booking<T, ID, Timestamp, Metadata, S>],
+    pub item_bookings: &'a [Booking<T, ID, Timestamp, Metadata, S>],
+}
+
+impl<'a, T, ID, Timestamp, Metadata, S> RuleContext<'a, T, ID, Timestamp, Metadata, S> {
+    /// Creates the context for creating a booking at `now`.
+    pub fn creation(now: &'a Timestamp) -> Self {
+        Self {
+            now: Some(now),
+            transition: None,
+            user_bookings: &[],
+            item_bookings: &[],
+        }
+    }
+
+    /// Creates the context for moving a booking through `transition`.
+    pub fn transition(transition: Transition<S>, now: Option<&'a Timestamp>) -> Self {
+        Self {
+            now,
+            transition: Some(transition),
+            user_bookings: &[],
+            item_bookings: &[],
+        }
+    }
+
+    /// Sets the other bookings of the same user.
+    pub fn with_user_bookings(mut self, bookings: &'a [Booking<T, ID, Timestamp, Metadata, S>]) -> Self {
+        self.user_bookings = bookings;
+        self
+    }
+
+    /// Sets the other bookings of the same item.
+    pub fn with_item_bookings(mut self, bookings: &'a [Booking<T, ID, Timestamp, Metadata, S>]) -> Self {
+        self.item_bookings = bookings;
+        self
+    }
+
+    /// Returns `true` if the booking is being created rather than transitioned.
+    pub fn is_creation(&self) -> bool {
+        self.transition.is_none()
+    }
+}
+
+/// The `BookingRule` trait defines a business rule a booking must satisfy.
+///
+/// `()` is the empty rule set and accepts every booking.
+pub trait BookingRule<T, ID, Timestamp, Metadata, S = BookingStatus> {
+    fn evaluate(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), RuleFailure>;
+
+    /// Returns every failure instead of stopping at the first one.
+    ///
+    /// Only composite rules such as `All` can report more than one failure;
+    /// the default implementation wraps `evaluate`.
+    fn evaluate_all(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Vec<RuleFailure> {
+        self.evaluate(booking, context).err().into_iter().collect()
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S> BookingRule<T, ID, Timestamp, Metadata, S> for () {
+    fn evaluate(
+        &self,
+        _booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        _context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), RuleFailure> {
+        Ok(())
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S, R> BookingRule<T, ID, Timestamp, Metadata, S> for Box<R>
+where
+    R: BookingRule<T, ID, Timestamp, Metadata, S> + ?Sized,
+{
+    fn evaluate(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), RuleFailure> {
+        (**self).evaluate(booking, context)
+    }
+
+    fn evaluate_all(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Vec<RuleFailure> {
+        (**self).evaluate_all(booking, context)
+    }
+}
+
+/// A rule defined by a closure. Created with `from_fn`.
+#[derive(Debug, Clone)]
+pub struct FnRule<F>(F);
+
+/// Creates a rule from a closure receiving the booking and the context.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::model::booking::Booking;
+/// use booking_kit::rules::{from_fn, BookingRule, RuleContext, RuleFailure};
+/// use booking_kit::traits::Bookable;
+///
+/// struct Room;
+/// impl Bookable for Room {
+///     fn id(&self) -> &str { "R101" }
+///     fn is_available(&self) -> bool { true }
+/// }
+///
+/// let members_only = from_fn(|booking: &Booking<Room, u32, i64, ()>, _: &RuleContext<'_, Room, u32, i64, ()>| {
+///     match booking.user_id {
+///         Some(_) => Ok(()),
+///         None => Err(RuleFailure::new("members_only", "guests must sign in to book")),
+///     }
+/// });
+///
+/// let guest = booking_kit::manager::BookingManager::create(1, None, Room, 0, None, None);
+/// assert!(members_only.evaluate(&guest, &RuleContext::creation(&0)).is_err());
+/// ```
+pub fn from_fn<F>(rule: F) -> FnRule<F> {
+    FnRule(rule)
+}
+
+impl<T, ID, Timestamp, Metadata, S, F> BookingRule<T, ID, Timestamp, Metadata, S> for FnRule<F>
+where
+    F: Fn(&Booking<T, ID, Timestamp, Metadata, S>, &RuleContext<'_, T, ID, Timestamp, Metadata, S>) -> Result<(), RuleFailure>,
+{
+    fn evaluate(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), RuleFailure> {
+        (self.0)(booking, context)
+    }
+}
+
+/// Passes if every inner rule passes; reports the first failure otherwise,
+/// or every failure through `evaluate_all`.
+#[derive(Debug, Clone)]
+pub struct All<R>(pub Vec<R>);
+
+impl<T, ID, Timestamp, Metadata, S, R> BookingRule<T, ID, Timestamp, Metadata, S> for All<R>
+where
+    R: BookingRule<T, ID, Timestamp, Metadata, S>,
+{
+    fn evaluate(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), RuleFailure> {
+        self.0.iter().try_for_each(|rule| rule.evaluate(booking, context))
+    }
+
+    fn evaluate_all(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Vec<RuleFailure> {
+        self.0.iter().flat_map(|rule| rule.evaluate_all(booking, context)).collect()
+    }
+}
+
+/// Passes if at least one inner rule passes; reports the first inner failure otherwise,
+/// or every inner failure through `evaluate_all`, so their codes reach a `ValidationReport`.
+///
+/// An empty `Any` never passes and reports an `"any"` failure.
+#[derive(Debug, Clone)]
+pub struct Any<R>(pub Vec<R>);
+
+impl<T, ID, Timestamp, Metadata, S, R> BookingRule<T, ID, Timestamp, Metadata, S> for Any<R>
+where
+    R: BookingRule<T, ID, Timestamp, Metadata, S>,
+{
+    fn evaluate(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), RuleFailure> {
+        let mut first = None;
+        for rule in &self.0 {
+            match rule.evaluate(booking, context) {
+                Ok(()) => return Ok(()),
+                Err(failure) => {
+                    first.get_or_insert(failure);
+                }
+            }
+        }
+        Err(first.unwrap_or_else(no_alternative))
+    }
+
+    fn evaluate_all(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Vec<RuleFailure> {
+        let mut failures = Vec::new();
+        for rule in &self.0 {
+            let inner = rule.evaluate_all(booking, context);
+            if inner.is_empty() {
+                return Vec::new();
+            }
+            failures.extend(inner);
+        }
+        if failures.is_empty() {
+            failures.push(no_alternative());
+        }
+        failures
+    }
+}
+
+/// The failure of an `Any` without inner rules.
+fn no_alternative() -> RuleFailure {
+    RuleFailure::new("any", "no alternative rule passed")
+}
+
+/// Passes if the inner rule fails, and reports the configured failure if it passes.
+#[derive(Debug, Clone)]
+pub struct Not<R> {
+    rule: R,
+    failure: RuleFailure,
+}
+
+impl<R> Not<R> {
+    /// Negates `rule`, reporting `code` and `message` when the inner rule passes.
+    pub fn new(rule: R, code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            rule,
+            failure: RuleFailure::new(code, message),
+        }
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S, R> BookingRule<T, ID, Timestamp, Metadata, S> for Not<R>
+where
+    R: BookingRule<T, ID, Timestamp, Metadata, S>,
+{
+    fn evaluate(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), RuleFailure> {
+        match self.rule.evaluate(booking, context) {
+            Ok(()) => Err(self.failure.clone()),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+/// Requires the reserved period to start at least the given duration after the booking is made.
+///
+/// Only evaluated on creation, for bookings with a period.
+#[derive(Debug, Clone)]
+pub struct MinLeadTime<D>(pub D);
+
+impl<T, ID, Timestamp, Metadata, S> BookingRule<T, ID, Timestamp, Metadata, S> for MinLeadTime<Timestamp::Duration>
+where
+    Timestamp: BookingTime,
+{
+    fn evaluate(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), RuleFailure> {
+        let (Some(now), Some(period), true) = (context.now, &booking.period, context.is_creation()) else {
+            return Ok(());
+        };
+
+        match period.start.elapsed_since(now) {
+            Some(lead) if lead >= self.0 => Ok(()),
+            _ => Err(RuleFailure::new(
+                "min_lead_time",
+                format!("booking must start at least {:?} in advance", self.0),
+            )),
+        }
+    }
+}
+
+/// Limits how far in advance the reserved period may start.
+///
+/// Only evaluated on creation, for bookings with a period.
+#[derive(Debug, Clone)]
+pub struct MaxAdvanceWindow<D>(pub D);
+
+impl<T, ID, Timestamp, Metadata, S> BookingRule<T, ID, Timestamp, Metadata, S> for MaxAdvanceWindow<Timestamp::Duration>
+where
+    Timestamp: BookingTime,
+{
+    fn evaluate(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), RuleFailure> {
+        let (Some(now), Some(period), true) = (context.now, &booking.period, context.is_creation()) else {
+            return Ok(());
+        };
+
+        match period.start.elapsed_since(now) {
+            Some(advance) if advance > self.0 => Err(RuleFailure::new(
+                "max_advance_window",
+                format!("booking cannot start more than {:?} in advance", self.0),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Limits the number of active bookings a single `user_id` may hold.
+///
+/// Evaluated on creation and on transitions that make a booking active again,
+/// counting the active bookings in `RuleContext::user_bookings`.
+#[derive(Debug, Clone)]
+pub struct MaxActivePerUser(pub usize);
+
+impl<T, ID, Timestamp, Metadata, S> BookingRule<T, ID, Timestamp, Metadata, S> for MaxActivePerUser
+where
+    ID: PartialEq,
+    S: Status,
+{
+    fn evaluate(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), RuleFailure> {
+        let activates = match &context.transition {
+            None => true,
+            Some(transition) => transition.to.is_active() && !transition.from.is_active(),
+        };
+        let Some(user_id) = booking.user_id.as_ref().filter(|_| activates) else {
+            return Ok(());
+        };
+
+        let active = context
+            .user_bookings
+            .iter()
+            .filter(|other| other.id != booking.id && other.user_id.as_ref() == Some(user_id) && other.status.is_active())
+            .count();
+
+        if active >= self.0 {
+            return Err(RuleFailure::new(
+                "max_active_per_user",
+                format!("user already holds {} active booking(s)", active),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Rejects bookings whose reserved period overlaps any of the blackout periods.
+///
+/// Only evaluated on creation, for bookings with a period.
+#[derive(Debug, Clone)]
+pub struct Blackout<Timestamp>(pub Vec<BookingPeriod<Timestamp>>);
+
+impl<T, ID, Timestamp, Metadata, S> BookingRule<T, ID, Timestamp, Metadata, S> for Blackout<Timestamp>
+where
+    T: Bookable,
+    Timestamp: PartialOrd,
+{
+    fn evaluate(
+        &self,
+        booking: &Booking<T, ID, Timestamp, Metadata, S>,
+        context: &RuleContext<'_, T, ID, Timestamp, Metadata, S>,
+    ) -> Result<(), RuleFailure> {
+        let (Some(period), true) = (&booking.period, context.is_creation()) else {
+            return Ok(());
+        };
+
+        if self.0.iter().any(|blackout| blackout.overlaps(period)) {
+            return Err(RuleFailure::new(
+                "blackout",
+                format!("item {} cannot be booked during a blackout period", booking.item_id()),
+            ));
+        }
+        Ok(())
+    }
+}
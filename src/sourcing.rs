@@ -0,0 +1,264 @@
+//! Event-sourced booking aggregate, rebuilding a `Booking` from an append-only log of events.
+
+use alloc::format;
+use alloc::vec::Vec;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::error::BookingError;
+use crate::model::booking::Booking;
+use crate::model::history::{ChangeContext, StatusChange};
+use crate::model::status::{BookingStatus, Status};
+use crate::traits::Bookable;
+
+/// An entry in the event log of a single booking.
+///
+/// # Variants:
+///
+/// - `Created`: The booking as it was created. Must be the first event of a log.
+/// - `Transitioned`: The booking moved between statuses, as recorded in its history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BookingLogEvent<T, ID, Timestamp, Metadata, S = BookingStatus> {
+    /// The booking as it was created. Must be the first event of a log.
+    Created(Booking<T, ID, Timestamp, Metadata, S>),
+
+    /// The booking moved between statuses, as recorded in its history.
+    Transitioned(StatusChange<ID, Timestamp, S>),
+}
+
+/// The state of a booking after the first `version` events of its log.
+///
+/// # Fields
+/// - `version`: Number of events folded into `booking`.
+/// - `booking`: The rebuilt booking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BookingSnapshot<T, ID, Timestamp, Metadata, S = BookingStatus> {
+    pub version: u64,
+    pub booking: Booking<T, ID, Timestamp, Metadata, S>,
+}
+
+/// A booking persisted as an append-only sequence of events instead of its latest state.
+///
+/// The current `Booking` is obtained by folding the events in order. The created booking must be
+/// in an initial status (see `Status::is_initial`) with no transitions yet, and every transition is
+/// validated against `Status::can_transition_to` and against the status the booking actually has,
+/// both when recording new events and when replaying a stored log, so a corrupted or tampered log
+/// is rejected rather than silently applied. Each applied transition bumps `Booking::version`, so
+/// it always trails the aggregate `version` by the `Created` event. Replaying can start from a
+/// `BookingSnapshot` to avoid folding the whole log.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::manager::BookingManager;
+/// use booking_kit::model::history::ChangeContext;
+/// use booking_kit::model::status::BookingStatus;
+/// use booking_kit::sourcing::BookingAggregate;
+/// use booking_kit::traits::Bookable;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Room;
+/// impl Bookable for Room {
+///     fn id(&self) -> &str { "R101" }
+///     fn is_available(&self) -> bool { true }
+/// }
+///
+/// let mut aggregate = BookingAggregate::new();
+/// aggregate.create(BookingManager::create(1, None, Room, 0_i64, None, None::<()>)).unwrap();
+/// aggregate.transition(BookingStatus::Confirmed, ChangeContext::new().at(10)).unwrap();
+///
+/// let log = aggregate.events().to_vec();
+/// let replayed = BookingAggregate::replay(log).unwrap();
+/// assert!(replayed.state().unwrap().is_confirmed());
+/// assert_eq!(replayed.version(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookingAggregate<T, ID, Timestamp, Metadata, S = BookingStatus> {
+    base_version: u64,
+    events: Vec<BookingLogEvent<T, ID, Timestamp, Metadata, S>>,
+    state: Option<Booking<T, ID, Timestamp, Metadata, S>>,
+}
+
+impl<T, ID, Timestamp, Metadata, S> Default for BookingAggregate<T, ID, Timestamp, Metadata, S> {
+    fn default() -> Self {
+        Self {
+            base_version: 0,
+            events: Vec::new(),
+            state: None,
+        }
+    }
+}
+
+impl<T, ID, Timestamp, Metadata, S> BookingAggregate<T, ID, Timestamp, Metadata, S>
+where
+    T: Bookable + Clone,
+    ID: Clone,
+    Timestamp: Clone,
+    Metadata: Clone,
+    S: Status,
+{
+    /// Creates an aggregate with an empty log.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an aggregate starting from a snapshot. Later events are recorded after it.
+    pub fn from_snapshot(snapshot: BookingSnapshot<T, ID, Timestamp, Metadata, S>) -> Self {
+        Self {
+            base_version: snapshot.version,
+            events: Vec::new(),
+            state: Some(snapshot.booking),
+        }
+    }
+
+    /// Rebuilds an aggregate by folding a full event log.
+    ///
+    /// Returns the same errors as `record` for the first event that cannot be applied.
+    pub fn replay<I>(events: I) -> Result<Self, BookingError>
+    where
+        I: IntoIterator<Item = BookingLogEvent<T, ID, Timestamp, Metadata, S>>,
+    {
+        let mut aggregate = Self::new();
+        for event in events {
+            aggregate.record(event)?;
+        }
+        Ok(aggregate)
+    }
+
+    /// Rebuilds an aggregate from a snapshot and the events recorded after it.
+    pub fn replay_from<I>(snapshot: BookingSnapshot<T, ID, Timestamp, Metadata, S>, events: I) -> Result<Self, BookingError>
+    where
+        I: IntoIterator<Item = BookingLogEvent<T, ID, Timestamp, Metadata, S>>,
+    {
+        let mut aggregate = Self::from_snapshot(snapshot);
+        for event in events {
+            aggregate.record(event)?;
+        }
+        Ok(aggregate)
+    }
+
+    /// Returns the booking after the first `version` events of a full log, or `None` if `version`
+    /// is zero.
+    pub fn state_at<I>(events: I, version: u64) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError>
+    where
+        I: IntoIterator<Item = BookingLogEvent<T, ID, Timestamp, Metadata, S>>,
+    {
+        let events = events.into_iter().take(usize::try_from(version).unwrap_or(usize::MAX));
+        Ok(Self::replay(events)?.state)
+    }
+
+    /// Returns the booking as it was at `at`, or `None` if it did not exist yet.
+    ///
+    /// `Created` happens at the booking's `created_at`, and a transition at the time recorded in
+    /// its status change. A transition without a time is considered to happen together with the
+    /// event before it.
+    pub fn state_as_of<I>(events: I, at: &Timestamp) -> Result<Option<Booking<T, ID, Timestamp, Metadata, S>>, BookingError>
+    where
+        I: IntoIterator<Item = BookingLogEvent<T, ID, Timestamp, Metadata, S>>,
+        Timestamp: PartialOrd,
+    {
+        let mut aggregate = Self::new();
+        for event in events {
+            let happened_at = match &event {
+                BookingLogEvent::Created(booking) => Some(&booking.created_at),
+                BookingLogEvent::Transitioned(change) => change.at.as_ref(),
+            };
+            if happened_at.is_some_and(|happened_at| happened_at > at) {
+                break;
+            }
+            aggregate.record(event)?;
+        }
+        Ok(aggregate.state)
+    }
+
+    /// Validates `event` against the current state, applies it, and appends it to the log.
+    ///
+    /// Returns `BookingError::GeneralError` if a `Created` event is recorded twice, creates a
+    /// booking that already has transitions, or a transition is recorded before it, and
+    /// `BookingError::InvalidStatus` if the booking is created in a status that is not initial.
+    /// A transition that does not start from the current status or is not allowed by
+    /// `Status::can_transition_to` returns `Status::transition_error`, i.e.
+    /// `BookingError::InvalidStatusTransition` for `BookingStatus`.
+    /// The aggregate is unchanged on error.
+    pub fn record(&mut self, event: BookingLogEvent<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError> {
+        match (&mut self.state, &event) {
+            (None, BookingLogEvent::Created(booking)) => {
+                if !booking.status.is_initial() {
+                    return Err(BookingError::new_invalid_status(&format!(
+                        "event {} creates a booking in non-initial status {:?}",
+                        self.version() + 1,
+                        booking.status
+                    )));
+                }
+                if !booking.history.is_empty() || booking.version != 0 {
+                    return Err(BookingError::new_general_error(&format!(
+                        "event {} creates a booking that has already been transitioned",
+                        self.version() + 1
+                    )));
+                }
+                self.state = Some(booking.clone());
+            }
+            (Some(_), BookingLogEvent::Created(_)) => {
+                return Err(BookingError::new_general_error(&format!(
+                    "event {} creates a booking that already exists",
+                    self.version() + 1
+                )));
+            }
+            (None, BookingLogEvent::Transitioned(_)) => {
+                return Err(BookingError::new_general_error(&format!(
+                    "event {} transitions a booking that was not created",
+                    self.version() + 1
+                )));
+            }
+            (Some(booking), BookingLogEvent::Transitioned(change)) => {
+                if booking.status != change.from || !change.from.can_transition_to(&change.to) {
+                    return Err(booking.status.transition_error(&change.to));
+                }
+                booking.status = change.to.clone();
+                booking.history.push(change.clone());
+                booking.version += 1;
+            }
+        }
+
+        self.events.push(event);
+        Ok(())
+    }
+
+    /// Records the creation of `booking`.
+    pub fn create(&mut self, booking: Booking<T, ID, Timestamp, Metadata, S>) -> Result<(), BookingError> {
+        self.record(BookingLogEvent::Created(booking))
+    }
+
+    /// Records a transition of the booking to the `to` status, described by `context`.
+    pub fn transition(&mut self, to: S, context: ChangeContext<ID, Timestamp>) -> Result<(), BookingError> {
+        let from = match &self.state {
+            Some(booking) => booking.status.clone(),
+            None => return Err(BookingError::new_general_error("cannot transition a booking that was not created")),
+        };
+        self.record(BookingLogEvent::Transitioned(context.into_change(from, to)))
+    }
+
+    /// Returns the current state of the booking, or `None` before it is created.
+    pub fn state(&self) -> Option<&Booking<T, ID, Timestamp, Metadata, S>> {
+        self.state.as_ref()
+    }
+
+    /// Returns the number of events folded into the current state, including those of the
+    /// snapshot the aggregate started from.
+    pub fn version(&self) -> u64 {
+        self.base_version + self.events.len() as u64
+    }
+
+    /// Returns the events recorded since the aggregate was created or loaded from a snapshot.
+    pub fn events(&self) -> &[BookingLogEvent<T, ID, Timestamp, Metadata, S>] {
+        &self.events
+    }
+
+    /// Returns a snapshot of the current state, or `None` before the booking is created.
+    pub fn snapshot(&self) -> Option<BookingSnapshot<T, ID, Timestamp, Metadata, S>> {
+        self.state.as_ref().map(|booking| BookingSnapshot {
+            version: self.version(),
+            booking: booking.clone(),
+        })
+    }
+}
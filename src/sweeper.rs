@@ -0,0 +1,180 @@
+//! Expiry sweeper transitioning overdue `Pending` bookings and holds to `Expired`.
+
+use alloc::vec::Vec;
+use core::fmt::Display;
+use crate::clock::Clock;
+use crate::error::BookingError;
+use crate::events::BookingSubscriber;
+use crate::manager::BookingManager;
+use crate::model::booking::Booking;
+use crate::model::history::ChangeContext;
+use crate::model::status::{BookingStatus, Status};
+use crate::policy::TransitionPolicy;
+use crate::repository::BookingRepository;
+use crate::rules::BookingRule;
+use crate::traits::Bookable;
+use crate::waitlist::BookingWaitlist;
+
+/// Reason recorded in the status history of bookings expired by the sweeper.
+pub const EXPIRY_REASON: &str = "expires_at has passed";
+
+/// Outcome of a single sweep.
+///
+/// # Fields
+/// - `swept_at`: The clock time the sweep was evaluated against.
+/// - `expired`: IDs of the bookings moved to `Expired`, in the order they were visited.
+/// - `skipped`: Overdue bookings that could not be expired, with the reason.
+#[derive(Debug)]
+pub struct SweepReport<ID, Timestamp> {
+    pub swept_at: Timestamp,
+    pub expired: Vec<ID>,
+    pub skipped: Vec<(ID, BookingError)>,
+}
+
+impl<ID, Timestamp> SweepReport<ID, Timestamp> {
+    /// Returns `true` if the sweep found no overdue booking: none was expired and none skipped.
+    pub fn is_empty(&self) -> bool {
+        self.expired.is_empty() && self.skipped.is_empty()
+    }
+}
+
+/// Finds `Pending` and `OnHold` bookings whose `expires_at` has passed and transitions them to `Expired`,
+/// releasing their items.
+///
+/// The current time is read from an injectable `Clock`, so hold timeouts can be tested
+/// deterministically with `FakeClock`. Each expiry is recorded in the booking's history with
+/// the sweep time and `EXPIRY_REASON`.
+///
+/// # Example
+/// ```rust
+/// # #[cfg(feature = "std")] {
+/// use booking_kit::clock::FakeClock;
+/// use booking_kit::manager::BookingManager;
+/// use booking_kit::sweeper::ExpirySweeper;
+/// use booking_kit::traits::Bookable;
+///
+/// struct Room { id: &'static str }
+/// impl Bookable for Room {
+///     fn id(&self) -> &str { self.id }
+///     fn is_available(&self) -> bool { true }
+/// }
+///
+/// let mut bookings = vec![
+///     BookingManager::create(1, None, Room { id: "R101" }, 0_i64, Some(600), None::<()>),
+///     BookingManager::create(2, None, Room { id: "R102" }, 0_i64, Some(1_800), None::<()>),
+/// ];
+///
+/// let clock = FakeClock::new(0_i64);
+/// let sweeper = ExpirySweeper::new(&clock);
+///
+/// clock.advance(900);
+/// let report = sweeper.sweep(&mut bookings);
+/// assert_eq!(report.expired, vec![1]);
+/// assert!(bookings[0].is_expired());
+/// assert!(bookings[1].is_pending());
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExpirySweeper<C> {
+    clock: C,
+}
+
+impl<C> ExpirySweeper<C> {
+    /// Creates a sweeper reading the current time from `clock`.
+    pub fn new(clock: C) -> Self {
+        Self { clock }
+    }
+
+    /// Returns the clock used by this sweeper.
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    /// Expires the overdue bookings in a collection the caller holds,
+    /// validating each transition through `TransitionPolicy::default()`.
+    ///
+    /// With a custom `Status` set, due bookings are skipped if the set has no `Expired` status.
+    pub fn sweep<T, ID, Timestamp, Metadata, S>(
+        &self,
+        bookings: &mut [Booking<T, ID, Timestamp, Metadata, S>],
+    ) -> SweepReport<ID, Timestamp>
+    where
+        C: Clock<Timestamp>,
+        S: Status,
+        T: Bookable,
+        ID: Clone,
+        Timestamp: PartialOrd + Clone,
+    {
+        let now = self.clock.now();
+        let policy = TransitionPolicy::default();
+        let mut report = SweepReport {
+            swept_at: now.clone(),
+            expired: Vec::new(),
+            skipped: Vec::new(),
+        };
+
+        for booking in bookings.iter_mut().filter(|b| is_due(b, &now)) {
+            let context = ChangeContext::new().at(now.clone()).because(EXPIRY_REASON);
+            let expired = S::try_from_booking_status(&BookingStatus::Expired);
+            match expired.and_then(|to| BookingManager::transition_with(&policy, booking, to, context)) {
+                Ok(()) => report.expired.push(booking.id.clone()),
+                Err(e) => report.skipped.push((booking.id.clone(), e)),
+            }
+        }
+
+        report
+    }
+
+    /// Expires the overdue bookings stored in the manager's repository,
+    /// validating each transition through the manager's policy and rules.
+    ///
+    /// Returns an error only if the repository cannot be listed; failures for individual
+    /// bookings are collected in `SweepReport::skipped`.
+    pub fn sweep_repository<R, Q, W, E, K, S, T, ID, Timestamp, Metadata>(
+        &self,
+        manager: &BookingManager<R, Q, W, E, K, S>,
+    ) -> Result<SweepReport<ID, Timestamp>, BookingError>
+    where
+        C: Clock<Timestamp>,
+        R: BookingRepository<T, ID, Timestamp, Metadata, S>,
+        Q: BookingRule<T, ID, Timestamp, Metadata, S>,
+        W: BookingWaitlist<ID>,
+        E: BookingSubscriber<ID, Timestamp, S>,
+        S: Status,
+        T: Bookable + Clone,
+        ID: PartialEq + Display + Clone,
+        Timestamp: PartialOrd + Clone,
+        Metadata: Clone,
+    {
+        let now = self.clock.now();
+        let mut report = SweepReport {
+            swept_at: now.clone(),
+            expired: Vec::new(),
+            skipped: Vec::new(),
+        };
+
+        let mut due = Vec::new();
+        for status in [BookingStatus::Pending, BookingStatus::OnHold].iter().filter_map(S::from_booking_status) {
+            due.extend(manager.repository().list_by_status(&status)?);
+        }
+        for booking in due.iter().filter(|b| is_due(b, &now)) {
+            let context = ChangeContext::new().at(now.clone()).because(EXPIRY_REASON);
+            let expired = S::try_from_booking_status(&BookingStatus::Expired);
+            match expired.and_then(|to| manager.transition_booking(&booking.id, to, context)) {
+                Ok(_) => report.expired.push(booking.id.clone()),
+                Err(e) => report.skipped.push((booking.id.clone(), e)),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+fn is_due<T, ID, Timestamp, Metadata, S>(booking: &Booking<T, ID, Timestamp, Metadata, S>, now: &Timestamp) -> bool
+where
+    S: Status,
+    T: Bookable,
+    Timestamp: PartialOrd,
+{
+    (booking.is_pending() || booking.is_on_hold()) && booking.is_past_expiry(now)
+}
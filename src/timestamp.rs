@@ -0,0 +1,198 @@
+//! The `BookingTime` trait, giving the generic `Timestamp` of a booking ordering and arithmetic.
+
+use core::fmt::Debug;
+#[cfg(feature = "std")]
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::clock::Clock;
+
+/// The `BookingTime` trait defines what the library needs from a timestamp type to reason
+/// about time: ordering and duration arithmetic. Reading the system time is left to
+/// `CurrentTime`, which requires the `std` feature.
+///
+/// `Booking` itself keeps `Timestamp` unconstrained, so plain strings still work for simple
+/// use cases; time-based features such as holds with a time-to-live and booking rules require
+/// `BookingTime`.
+///
+/// Implemented for:
+/// - `i64` as unix seconds, with `i64` seconds as duration.
+/// - `std::time::SystemTime`, with `std::time::Duration` (feature `std`).
+/// - `chrono::DateTime<Utc>` with `chrono::TimeDelta` (feature `chrono`).
+/// - `time::OffsetDateTime` with `time::Duration` (feature `time`).
+/// - `jiff::Timestamp` with `jiff::SignedDuration` (feature `jiff`).
+///
+/// # Example
+/// ```rust
+/// use booking_kit::timestamp::BookingTime;
+///
+/// let check_in: i64 = 1_700_000_000;
+/// let check_out = check_in.checked_plus(&(2 * 24 * 60 * 60)).unwrap();
+///
+/// assert_eq!(check_out.elapsed_since(&check_in), Some(172_800));
+/// assert_eq!(check_in.elapsed_since(&check_out), None);
+/// ```
+pub trait BookingTime: Ord + Clone + Sized {
+    /// The span of time between two timestamps.
+    type Duration: Ord + Clone + Debug;
+
+    /// Returns the current time according to `clock`.
+    fn now_from<C: Clock<Self> + ?Sized>(clock: &C) -> Self {
+        clock.now()
+    }
+
+    /// Returns the timestamp `duration` after `self`, or `None` on overflow.
+    fn checked_plus(&self, duration: &Self::Duration) -> Option<Self>;
+
+    /// Returns the timestamp `duration` before `self`, or `None` on overflow.
+    fn checked_minus(&self, duration: &Self::Duration) -> Option<Self>;
+
+    /// Returns the time elapsed from `earlier` to `self`, or `None` if `earlier` is after `self`.
+    fn elapsed_since(&self, earlier: &Self) -> Option<Self::Duration>;
+}
+
+/// The `CurrentTime` trait reads the system time as a `BookingTime`, e.g. for `SystemClock`.
+/// Requires the `std` feature.
+///
+/// Implemented for every `BookingTime` type listed there.
+#[cfg(feature = "std")]
+pub trait CurrentTime: BookingTime {
+    /// Returns the current system time.
+    fn now() -> Self;
+}
+
+impl BookingTime for i64 {
+    type Duration = i64;
+
+    fn checked_plus(&self, duration: &i64) -> Option<Self> {
+        self.checked_add(*duration)
+    }
+
+    fn checked_minus(&self, duration: &i64) -> Option<Self> {
+        self.checked_sub(*duration)
+    }
+
+    fn elapsed_since(&self, earlier: &Self) -> Option<i64> {
+        if earlier > self {
+            return None;
+        }
+        self.checked_sub(*earlier)
+    }
+}
+
+#[cfg(feature = "std")]
+impl CurrentTime for i64 {
+    fn now() -> Self {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_secs() as i64,
+            // Rounds down like after the epoch, e.g. 1.5 s before it is -2, not -1.
+            Err(before_epoch) => {
+                let before = before_epoch.duration();
+                -(before.as_secs() as i64) - i64::from(before.subsec_nanos() > 0)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl BookingTime for SystemTime {
+    type Duration = Duration;
+
+    fn checked_plus(&self, duration: &Duration) -> Option<Self> {
+        self.checked_add(*duration)
+    }
+
+    fn checked_minus(&self, duration: &Duration) -> Option<Self> {
+        self.checked_sub(*duration)
+    }
+
+    fn elapsed_since(&self, earlier: &Self) -> Option<Duration> {
+        self.duration_since(*earlier).ok()
+    }
+}
+
+#[cfg(feature = "std")]
+impl CurrentTime for SystemTime {
+    fn now() -> Self {
+        SystemTime::now()
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl BookingTime for chrono::DateTime<chrono::Utc> {
+    type Duration = chrono::TimeDelta;
+
+    fn checked_plus(&self, duration: &chrono::TimeDelta) -> Option<Self> {
+        self.checked_add_signed(*duration)
+    }
+
+    fn checked_minus(&self, duration: &chrono::TimeDelta) -> Option<Self> {
+        self.checked_sub_signed(*duration)
+    }
+
+    fn elapsed_since(&self, earlier: &Self) -> Option<chrono::TimeDelta> {
+        if earlier > self {
+            return None;
+        }
+        Some(self.signed_duration_since(*earlier))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl CurrentTime for chrono::DateTime<chrono::Utc> {
+    fn now() -> Self {
+        chrono::Utc::now()
+    }
+}
+
+#[cfg(feature = "time")]
+impl BookingTime for time::OffsetDateTime {
+    type Duration = time::Duration;
+
+    fn checked_plus(&self, duration: &time::Duration) -> Option<Self> {
+        self.checked_add(*duration)
+    }
+
+    fn checked_minus(&self, duration: &time::Duration) -> Option<Self> {
+        self.checked_sub(*duration)
+    }
+
+    fn elapsed_since(&self, earlier: &Self) -> Option<time::Duration> {
+        if earlier > self {
+            return None;
+        }
+        Some(*self - *earlier)
+    }
+}
+
+#[cfg(feature = "time")]
+impl CurrentTime for time::OffsetDateTime {
+    fn now() -> Self {
+        time::OffsetDateTime::now_utc()
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl BookingTime for jiff::Timestamp {
+    type Duration = jiff::SignedDuration;
+
+    fn checked_plus(&self, duration: &jiff::SignedDuration) -> Option<Self> {
+        self.checked_add(*duration).ok()
+    }
+
+    fn checked_minus(&self, duration: &jiff::SignedDuration) -> Option<Self> {
+        self.checked_sub(*duration).ok()
+    }
+
+    fn elapsed_since(&self, earlier: &Self) -> Option<jiff::SignedDuration> {
+        if earlier > self {
+            return None;
+        }
+        Some(self.duration_since(*earlier))
+    }
+}
+
+#[cfg(feature = "jiff")]
+impl CurrentTime for jiff::Timestamp {
+    fn now() -> Self {
+        jiff::Timestamp::now()
+    }
+}
@@ -6,4 +6,13 @@
 pub trait Bookable {
     fn id(&self) -> &str;
     fn is_available(&self) -> bool;
+
+    /// Returns the total number of units that can be reserved at the same time, if limited.
+    ///
+    /// Capacity-managed items (seats on a flight segment, tickets for an event) override this
+    /// so that bookings carrying a quantity are summed against it. The default, `None`, treats
+    /// the item as an exclusive resource that cannot be double-booked for overlapping periods.
+    fn capacity(&self) -> Option<u32> {
+        None
+    }
 }
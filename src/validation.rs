@@ -0,0 +1,115 @@
+//! Aggregated validation results, reporting every reason a booking was rejected at once.
+
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use crate::error::BookingError;
+use crate::rules::RuleFailure;
+
+/// A single reason a booking failed validation.
+///
+/// # Fields
+/// - `code`: Machine-readable code; the rule's own code (e.g. `"min_lead_time"`) for rule
+///   failures, and `BookingError::code()` for everything else (e.g. `"item_unavailable"`).
+/// - `message`: Human-readable explanation.
+/// - `error`: The underlying error. Not serialized.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ValidationIssue {
+    pub code: String,
+    pub message: String,
+
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub error: BookingError,
+}
+
+impl From<BookingError> for ValidationIssue {
+    fn from(error: BookingError) -> Self {
+        Self {
+            code: error.code().to_string(),
+            message: error.to_string(),
+            error,
+        }
+    }
+}
+
+impl From<RuleFailure> for ValidationIssue {
+    fn from(failure: RuleFailure) -> Self {
+        Self {
+            code: failure.code.clone(),
+            message: failure.message.clone(),
+            error: failure.into(),
+        }
+    }
+}
+
+/// Collects every `BookingError` found while validating a booking, instead of failing fast.
+///
+/// Returned by `BookingManager::validate` and `BookingManager::try_insert_reporting`.
+/// Serializes to `{"issues": [{"code": ..., "message": ...}, ...]}` for API responses.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Creates an empty report.
+    pub fn new() -> Self {
+        Self { issues: Vec::new() }
+    }
+
+    /// Adds an issue to the report.
+    pub fn push(&mut self, issue: impl Into<ValidationIssue>) {
+        self.issues.push(issue.into());
+    }
+
+    /// Returns `true` if no issue was found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the codes of all issues, in the order they were found.
+    pub fn codes(&self) -> Vec<&str> {
+        self.issues.iter().map(|issue| issue.code.as_str()).collect()
+    }
+
+    /// Returns `true` if an issue with the given code was found.
+    pub fn contains(&self, code: &str) -> bool {
+        self.issues.iter().any(|issue| issue.code == code)
+    }
+
+    /// Returns the underlying errors, in the order they were found.
+    pub fn errors(&self) -> impl Iterator<Item = &BookingError> {
+        self.issues.iter().map(|issue| &issue.error)
+    }
+
+    /// Returns `Ok(())` for a valid report, or the report itself as the error.
+    pub fn into_result(self) -> Result<(), ValidationReport> {
+        if self.is_valid() {
+            return Ok(());
+        }
+        Err(self)
+    }
+}
+
+impl From<BookingError> for ValidationReport {
+    fn from(error: BookingError) -> Self {
+        Self { issues: vec![error.into()] }
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Booking validation failed with {} issue(s)", self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n- [{}] {}", issue.code, issue.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl core::error::Error for ValidationReport {}
@@ -0,0 +1,213 @@
+//! Waitlist of bookings waiting for an item to free up, keyed by `Bookable::id()`.
+
+use alloc::format;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use alloc::string::ToString;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::error::BookingError;
+
+/// Reason recorded in the status history of bookings promoted from the waitlist.
+pub const PROMOTION_REASON: &str = "promoted from waitlist";
+
+/// The order in which waitlisted bookings are promoted.
+///
+/// # Variants:
+///
+/// - `Fifo`: First come, first served.
+/// - `Priority`: Highest priority first; entries with the same priority are served first come,
+///   first served.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WaitlistOrder {
+    /// First come, first served.
+    #[default]
+    Fifo,
+
+    /// Highest priority first, then first come, first served.
+    Priority,
+}
+
+/// A booking waiting for its item, as stored in a `Waitlist`.
+///
+/// # Fields
+/// - `booking_id`: ID of the stored booking in `Waitlisted` status.
+/// - `item_id`: `Bookable::id()` of the item the booking waits for.
+/// - `priority`: Priority of the entry; higher is served first with `WaitlistOrder::Priority`.
+/// - `sequence`: Position in which the entry was added, used for first come, first served.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct WaitlistEntry<ID> {
+    pub booking_id: ID,
+    pub item_id: String,
+    pub priority: i32,
+    pub sequence: u64,
+}
+
+/// The `BookingWaitlist` trait defines how a `BookingManager` queues and promotes bookings
+/// that could not be reserved.
+///
+/// The waitlist only keeps the order; the bookings themselves are stored in the repository in
+/// `Waitlisted` status. `()` is the default and has no waitlist: nothing is ever promoted and
+/// enqueueing fails.
+pub trait BookingWaitlist<ID> {
+    /// Adds a booking to the waitlist of the given item.
+    fn enqueue(&self, item_id: &str, booking_id: ID, priority: i32) -> Result<(), BookingError>;
+
+    /// Returns the IDs of the bookings waiting for the given item, in promotion order.
+    fn candidates(&self, item_id: &str) -> Result<Vec<ID>, BookingError>;
+
+    /// Removes a booking from the waitlist. Returns `true` if it was waiting.
+    fn remove(&self, booking_id: &ID) -> Result<bool, BookingError>;
+}
+
+impl<ID> BookingWaitlist<ID> for () {
+    fn enqueue(&self, item_id: &str, _booking_id: ID, _priority: i32) -> Result<(), BookingError> {
+        Err(BookingError::new_creation_failed(&format!("no waitlist configured for item {item_id}")))
+    }
+
+    fn candidates(&self, _item_id: &str) -> Result<Vec<ID>, BookingError> {
+        Ok(Vec::new())
+    }
+
+    fn remove(&self, _booking_id: &ID) -> Result<bool, BookingError> {
+        Ok(false)
+    }
+}
+
+/// A thread-safe in-memory `BookingWaitlist` with one queue per item. Requires the `std` feature.
+///
+/// # Example
+/// ```rust
+/// use booking_kit::waitlist::{BookingWaitlist, Waitlist};
+///
+/// let waitlist = Waitlist::by_priority();
+/// waitlist.enqueue("R101", 1, 0).unwrap();
+/// waitlist.enqueue("R101", 2, 10).unwrap();
+///
+/// assert_eq!(waitlist.candidates("R101").unwrap(), vec![2, 1]);
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct Waitlist<ID> {
+    order: WaitlistOrder,
+    state: Mutex<WaitlistState<ID>>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug)]
+struct WaitlistState<ID> {
+    next_sequence: u64,
+    queues: HashMap<String, Vec<WaitlistEntry<ID>>>,
+}
+
+#[cfg(feature = "std")]
+impl<ID> Default for WaitlistState<ID> {
+    fn default() -> Self {
+        Self {
+            next_sequence: 0,
+            queues: HashMap::new(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<ID> Waitlist<ID> {
+    /// Creates an empty waitlist promoting entries in the given order.
+    pub fn new(order: WaitlistOrder) -> Self {
+        Self {
+            order,
+            state: Mutex::new(WaitlistState::default()),
+        }
+    }
+
+    /// Creates an empty first come, first served waitlist.
+    pub fn fifo() -> Self {
+        Self::new(WaitlistOrder::Fifo)
+    }
+
+    /// Creates an empty waitlist serving the highest priority first.
+    pub fn by_priority() -> Self {
+        Self::new(WaitlistOrder::Priority)
+    }
+
+    /// Returns the order in which entries are promoted.
+    pub fn order(&self) -> WaitlistOrder {
+        self.order
+    }
+
+    /// Returns the entries waiting for the given item, in promotion order.
+    pub fn entries(&self, item_id: &str) -> Result<Vec<WaitlistEntry<ID>>, BookingError>
+    where
+        ID: Clone,
+    {
+        let state = self.state.lock().map_err(|e| BookingError::new_storage_error(&e.to_string()))?;
+        let mut entries = state.queues.get(item_id).cloned().unwrap_or_default();
+        match self.order {
+            WaitlistOrder::Fifo => entries.sort_by_key(|entry| entry.sequence),
+            WaitlistOrder::Priority => entries.sort_by(|a, b| b.priority.cmp(&a.priority).then(a.sequence.cmp(&b.sequence))),
+        }
+        Ok(entries)
+    }
+
+    /// Returns the number of waiting entries across all items.
+    pub fn len(&self) -> usize {
+        self.state
+            .lock()
+            .map(|state| state.queues.values().map(Vec::len).sum())
+            .unwrap_or(0)
+    }
+
+    /// Returns `true` if nothing is waiting.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(feature = "std")]
+impl<ID> Default for Waitlist<ID> {
+    fn default() -> Self {
+        Self::fifo()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<ID> BookingWaitlist<ID> for Waitlist<ID>
+where
+    ID: PartialEq + Clone,
+{
+    fn enqueue(&self, item_id: &str, booking_id: ID, priority: i32) -> Result<(), BookingError> {
+        let mut state = self.state.lock().map_err(|e| BookingError::new_storage_error(&e.to_string()))?;
+        let sequence = state.next_sequence;
+        state.next_sequence += 1;
+        state.queues.entry(item_id.to_string()).or_default().push(WaitlistEntry {
+            booking_id,
+            item_id: item_id.to_string(),
+            priority,
+            sequence,
+        });
+        Ok(())
+    }
+
+    fn candidates(&self, item_id: &str) -> Result<Vec<ID>, BookingError> {
+        Ok(self.entries(item_id)?.into_iter().map(|entry| entry.booking_id).collect())
+    }
+
+    fn remove(&self, booking_id: &ID) -> Result<bool, BookingError> {
+        let mut state = self.state.lock().map_err(|e| BookingError::new_storage_error(&e.to_string()))?;
+        let mut removed = false;
+        for queue in state.queues.values_mut() {
+            let before = queue.len();
+            queue.retain(|entry| &entry.booking_id != booking_id);
+            removed |= queue.len() != before;
+        }
+        state.queues.retain(|_, queue| !queue.is_empty());
+        Ok(removed)
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::traits::Bookable;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct FlightSegment {
+        pub id: &'static str,
+        pub seats: u32,
+    }
+
+    impl Bookable for FlightSegment {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            self.seats > 0
+        }
+
+        fn capacity(&self) -> Option<u32> {
+            Some(self.seats)
+        }
+    }
+
+    type Repo = InMemoryBookingRepository<FlightSegment, u32, i64, ()>;
+
+    fn seats(id: u32, quantity: u32) -> Booking<FlightSegment, u32, i64, ()> {
+        let segment = FlightSegment { id: "GA123-CGK-DPS", seats: 5 };
+        BookingManager::create(id, None, segment, 0, None, None).with_quantity(quantity)
+    }
+
+    #[test]
+    fn test_quantities_are_summed_against_capacity() {
+        let manager = BookingManager::new(Repo::new());
+        manager.try_insert(seats(1, 3)).unwrap();
+        manager.try_insert(seats(2, 2)).unwrap();
+
+        let result = manager.try_insert(seats(3, 1));
+
+        assert!(matches!(result, Err(BookingError::QuantityExceeded)));
+        assert_eq!(manager.repository().len(), 2);
+    }
+
+    #[test]
+    fn test_single_booking_above_capacity_is_rejected() {
+        let manager = BookingManager::new(Repo::new());
+
+        assert!(matches!(manager.try_insert(seats(1, 6)), Err(BookingError::QuantityExceeded)));
+    }
+
+    #[test]
+    fn test_canceled_bookings_release_capacity() {
+        let manager = BookingManager::new(Repo::new());
+        manager.try_insert(seats(1, 5)).unwrap();
+        manager.cancel_booking(&1).unwrap();
+
+        assert!(manager.try_insert(seats(2, 5)).is_ok());
+    }
+
+    #[test]
+    fn test_capacity_is_shared_only_within_overlapping_periods() {
+        let manager = BookingManager::new(Repo::new());
+        manager.try_insert(seats(1, 5).with_period(0, 10)).unwrap();
+
+        assert!(manager.try_insert(seats(2, 5).with_period(10, 20)).is_ok());
+        assert!(matches!(
+            manager.try_insert(seats(3, 1).with_period(5, 15)),
+            Err(BookingError::QuantityExceeded)
+        ));
+    }
+
+    #[test]
+    fn test_zero_quantity_is_rejected() {
+        let result = BookingManager::check_capacity(&seats(1, 0), &[]);
+
+        assert!(matches!(result, Err(BookingError::CreationFailed(_))));
+    }
+}
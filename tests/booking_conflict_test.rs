@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+    use std::time::Duration;
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::traits::Bookable;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Room {
+        pub id: &'static str,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    /// A room whose capacity lookup is slow, widening the window between checking for conflicts
+    /// and storing the booking.
+    #[derive(Debug, Clone, PartialEq)]
+    struct SlowRoom;
+
+    impl Bookable for SlowRoom {
+        fn id(&self) -> &str {
+            "room-1"
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn capacity(&self) -> Option<u32> {
+            thread::sleep(Duration::from_millis(1));
+            None
+        }
+    }
+
+    type Repo = InMemoryBookingRepository<Room, u32, i64, ()>;
+
+    fn stay(id: u32, room: &'static str, start: i64, end: i64) -> Booking<Room, u32, i64, ()> {
+        BookingManager::create(id, None, Room { id: room }, 0, None, None).with_period(start, end)
+    }
+
+    #[test]
+    fn test_overlapping_booking_is_rejected_with_conflicting_id() {
+        let manager = BookingManager::new(Repo::new());
+        manager.try_insert(stay(1, "room-1", 10, 20)).unwrap();
+
+        let result = manager.try_insert(stay(2, "room-1", 15, 25));
+
+        match result {
+            Err(BookingError::BookingConflict { item_id, booking_id }) => {
+                assert_eq!(item_id, "room-1");
+                assert_eq!(booking_id, "1");
+            }
+            other => panic!("Expected BookingConflict error, got {:?}", other),
+        }
+        assert_eq!(manager.repository().len(), 1);
+    }
+
+    #[test]
+    fn test_back_to_back_periods_do_not_conflict() {
+        let manager = BookingManager::new(Repo::new());
+        manager.try_insert(stay(1, "room-1", 10, 20)).unwrap();
+
+        assert!(manager.try_insert(stay(2, "room-1", 20, 30)).is_ok());
+    }
+
+    #[test]
+    fn test_other_items_do_not_conflict() {
+        let manager = BookingManager::new(Repo::new());
+        manager.try_insert(stay(1, "room-1", 10, 20)).unwrap();
+
+        assert!(manager.try_insert(stay(2, "room-2", 10, 20)).is_ok());
+    }
+
+    #[test]
+    fn test_canceled_booking_releases_period() {
+        let manager = BookingManager::new(Repo::new());
+        manager.try_insert(stay(1, "room-1", 10, 20)).unwrap();
+        manager.cancel_booking(&1).unwrap();
+
+        assert!(manager.try_insert(stay(2, "room-1", 10, 20)).is_ok());
+    }
+
+    #[test]
+    fn test_inverted_period_is_rejected() {
+        let manager = BookingManager::new(Repo::new());
+        let result = manager.try_insert(stay(1, "room-1", 20, 10));
+
+        assert!(matches!(result, Err(BookingError::CreationFailed(_))));
+    }
+
+    #[test]
+    fn test_check_conflicts_without_repository() {
+        let existing = vec![stay(1, "room-1", 10, 20)];
+
+        assert!(BookingManager::check_conflicts(&stay(2, "room-1", 0, 10), &existing).is_ok());
+        assert!(BookingManager::check_conflicts(&stay(3, "room-1", 19, 21), &existing).is_err());
+    }
+
+    #[test]
+    fn test_try_create_against_rejects_double_booking_without_repository() {
+        let existing = vec![stay(1, "room-1", 10, 20)];
+
+        let booking = BookingManager::try_create_against(stay(2, "room-1", 20, 30), &existing).unwrap();
+        assert_eq!(booking.id, 2);
+        match BookingManager::try_create_against(stay(3, "room-1", 15, 25), &existing) {
+            Err(BookingError::BookingConflict { item_id, booking_id }) => {
+                assert_eq!(item_id, "room-1");
+                assert_eq!(booking_id, "1");
+            }
+            other => panic!("Expected BookingConflict error, got {:?}", other),
+        }
+        assert!(matches!(
+            BookingManager::try_create_against(stay(4, "room-1", 30, 25), &existing),
+            Err(BookingError::CreationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_concurrent_overlapping_bookings_cannot_both_be_stored() {
+        for _ in 0..20 {
+            let manager = Arc::new(BookingManager::new(InMemoryBookingRepository::new()));
+            let barrier = Arc::new(Barrier::new(2));
+
+            let handles: Vec<_> = [(1, 10, 20), (2, 15, 25)]
+                .into_iter()
+                .map(|(id, start, end)| {
+                    let (manager, barrier) = (Arc::clone(&manager), Arc::clone(&barrier));
+                    thread::spawn(move || {
+                        barrier.wait();
+                        manager.try_insert(BookingManager::create(id, None, SlowRoom, 0_i64, None, None::<()>).with_period(start, end))
+                    })
+                })
+                .collect();
+            let results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+            assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+            assert!(results.iter().any(|result| matches!(result, Err(BookingError::BookingConflict { .. }))));
+            assert_eq!(manager.repository().len(), 1);
+        }
+    }
+}
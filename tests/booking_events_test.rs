@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use booking_kit::clock::FakeClock;
+    use booking_kit::events::{from_fn, BookingEvent, BookingEventData, ChannelSubscriber, EventDispatcher};
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::history::ChangeContext;
+    use booking_kit::model::status::BookingStatus;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::sweeper::ExpirySweeper;
+    use booking_kit::traits::Bookable;
+    use booking_kit::waitlist::Waitlist;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Room {
+        pub id: &'static str,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    type Events = Arc<Mutex<Vec<BookingEvent<u32, i64>>>>;
+
+    fn recorder() -> (Events, EventDispatcher<u32, i64>) {
+        let events: Events = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&events);
+        let dispatcher = EventDispatcher::new().subscribe(from_fn(move |event: &BookingEvent<u32, i64>| {
+            recorded.lock().unwrap().push(event.clone());
+        }));
+        (events, dispatcher)
+    }
+
+    fn night(booking_id: u32) -> Booking<Room, u32, i64, ()> {
+        BookingManager::create(booking_id, None, Room { id: "room-1" }, 0, Some(600), None).with_period(100, 200)
+    }
+
+    #[test]
+    fn test_lifecycle_operations_emit_events() {
+        let (events, dispatcher) = recorder();
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_subscriber(dispatcher);
+
+        manager.try_insert(night(1)).unwrap();
+        manager.confirm_booking(&1).unwrap();
+        manager
+            .transition_booking(&1, BookingStatus::Canceled, ChangeContext::new().at(50))
+            .unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0],
+            BookingEvent::Created(BookingEventData {
+                booking_id: 1,
+                item_id: "room-1".into(),
+                from: None,
+                to: BookingStatus::Pending,
+                at: Some(0),
+            })
+        );
+        assert!(matches!(&events[1], BookingEvent::Confirmed(data) if data.from == Some(BookingStatus::Pending)));
+        assert!(matches!(&events[2], BookingEvent::Canceled(data) if data.at == Some(50)));
+        assert_eq!(events[2].booking_id(), &1);
+        assert_eq!(events[2].item_id(), "room-1");
+    }
+
+    #[test]
+    fn test_extended_lifecycle_has_its_own_events() {
+        let (events, dispatcher) = recorder();
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_subscriber(dispatcher);
+        for booking_id in 1..=3 {
+            manager.try_insert(night(booking_id).with_period(booking_id as i64 * 100, booking_id as i64 * 100 + 50)).unwrap();
+            manager.confirm_booking(&booking_id).unwrap();
+        }
+
+        manager.check_in_booking(&1).unwrap();
+        manager.mark_no_show_booking(&2).unwrap();
+        manager.refund_booking(&3).unwrap();
+
+        let events = events.lock().unwrap();
+        assert!(matches!(&events[6], BookingEvent::CheckedIn(data) if data.booking_id == 1 && data.from == Some(BookingStatus::Confirmed)));
+        assert!(matches!(&events[7], BookingEvent::NoShow(data) if data.booking_id == 2 && data.to == BookingStatus::NoShow));
+        assert!(matches!(&events[8], BookingEvent::Refunded(data) if data.booking_id == 3 && data.to == BookingStatus::Refunded));
+    }
+
+    #[test]
+    fn test_failed_operations_emit_nothing() {
+        let (events, dispatcher) = recorder();
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_subscriber(dispatcher);
+        manager.try_insert(night(1)).unwrap();
+
+        assert!(manager.try_insert(night(2)).is_err());
+        assert!(manager.complete_booking(&1).is_err());
+        assert!(manager.confirm_booking(&9).is_err());
+
+        assert_eq!(events.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_promotion_is_emitted_after_cancellation() {
+        let (events, dispatcher) = recorder();
+        let manager = BookingManager::new(InMemoryBookingRepository::new())
+            .with_waitlist(Waitlist::fifo())
+            .with_subscriber(dispatcher);
+        manager.try_insert(night(1)).unwrap();
+        manager.try_create_or_waitlist(night(2), 0).unwrap();
+
+        manager.cancel_booking(&1).unwrap();
+
+        let events = events.lock().unwrap();
+        assert!(matches!(&events[1], BookingEvent::Created(data) if data.to == BookingStatus::Waitlisted));
+        assert!(matches!(&events[2], BookingEvent::Canceled(data) if data.booking_id == 1));
+        assert!(matches!(
+            &events[3],
+            BookingEvent::Modified(data) if data.booking_id == 2 && data.to == BookingStatus::Pending
+        ));
+    }
+
+    #[test]
+    fn test_sweeper_expiry_is_emitted() {
+        let (events, dispatcher) = recorder();
+        let clock = FakeClock::new(0_i64);
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_subscriber(dispatcher);
+        manager.try_insert(night(1)).unwrap();
+
+        clock.advance(600);
+        ExpirySweeper::new(&clock).sweep_repository(&manager).unwrap();
+
+        let events = events.lock().unwrap();
+        assert!(matches!(&events[1], BookingEvent::Expired(data) if data.at == Some(600)));
+    }
+
+    #[test]
+    fn test_dispatcher_notifies_subscribers_in_order() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let (first, second) = (Arc::clone(&seen), Arc::clone(&seen));
+        let dispatcher = EventDispatcher::new()
+            .subscribe(from_fn(move |_: &BookingEvent<u32, i64>| first.lock().unwrap().push("email")))
+            .subscribe(from_fn(move |_: &BookingEvent<u32, i64>| second.lock().unwrap().push("analytics")));
+        assert_eq!(dispatcher.len(), 2);
+
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_subscriber(dispatcher);
+        manager.try_insert(night(1)).unwrap();
+
+        assert_eq!(*seen.lock().unwrap(), vec!["email", "analytics"]);
+    }
+
+    #[test]
+    fn test_channel_subscriber_delivers_to_another_thread() {
+        let (subscriber, receiver) = ChannelSubscriber::new();
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_subscriber(subscriber);
+
+        let consumer = thread::spawn(move || receiver.iter().map(|event| event.data().to.clone()).collect::<Vec<_>>());
+        manager.try_insert(night(1)).unwrap();
+        manager.confirm_booking(&1).unwrap();
+        drop(manager);
+
+        assert_eq!(consumer.join().unwrap(), vec![BookingStatus::Pending, BookingStatus::Confirmed]);
+    }
+
+    #[test]
+    fn test_event_serde_round_trip() {
+        let booking = night(1);
+        let event = BookingEvent::created(&booking);
+
+        let json = serde_json::to_string(&event).unwrap();
+        let restored: BookingEvent<u32, i64> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, event);
+    }
+}
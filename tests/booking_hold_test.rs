@@ -0,0 +1,166 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+    use std::time::Duration;
+    use booking_kit::clock::FakeClock;
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::status::BookingStatus;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::sweeper::ExpirySweeper;
+    use booking_kit::traits::Bookable;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct ConcertTicket {
+        pub remaining: u32,
+    }
+
+    impl Bookable for ConcertTicket {
+        fn id(&self) -> &str {
+            "concert-2025"
+        }
+
+        fn is_available(&self) -> bool {
+            self.remaining > 0
+        }
+
+        fn capacity(&self) -> Option<u32> {
+            Some(self.remaining)
+        }
+    }
+
+    /// A single ticket whose capacity lookup is slow, widening the window between checking
+    /// availability and storing a hold.
+    #[derive(Debug, Clone, PartialEq)]
+    struct LastTicket;
+
+    impl Bookable for LastTicket {
+        fn id(&self) -> &str {
+            "concert-2026"
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn capacity(&self) -> Option<u32> {
+            thread::sleep(Duration::from_millis(1));
+            Some(1)
+        }
+    }
+
+    const TTL: i64 = 5 * 60;
+
+    fn ticket(booking_id: u32, created_at: i64) -> Booking<ConcertTicket, u32, i64, ()> {
+        BookingManager::create(booking_id, Some(booking_id), ConcertTicket { remaining: 1 }, created_at, None, None)
+    }
+
+    #[test]
+    fn test_hold_counts_against_availability() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+
+        let hold = manager.try_hold(ticket(1, 0), &TTL).unwrap();
+
+        assert!(hold.is_on_hold());
+        assert!(hold.is_active());
+        assert_eq!(hold.expires_at, Some(TTL));
+        assert!(matches!(manager.try_hold(ticket(2, 60), &TTL), Err(BookingError::QuantityExceeded)));
+        assert!(matches!(manager.try_insert(ticket(3, 60)), Err(BookingError::QuantityExceeded)));
+    }
+
+    #[test]
+    fn test_lapsed_hold_no_longer_counts_against_availability() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+        manager.try_hold(ticket(1, 0), &TTL).unwrap();
+
+        let booking = manager.try_insert(ticket(2, TTL)).unwrap();
+
+        assert!(booking.is_pending());
+        assert!(manager.repository().get(&1).unwrap().unwrap().is_on_hold());
+    }
+
+    #[test]
+    fn test_convert_hold_into_confirmed_booking() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+        manager.try_hold(ticket(1, 0), &TTL).unwrap();
+
+        let booking = manager.convert_hold(&1, BookingStatus::Confirmed, 120).unwrap();
+
+        assert!(booking.is_confirmed());
+        assert_eq!(booking.expires_at, None);
+        let change = booking.last_change().unwrap();
+        assert_eq!(change.from, BookingStatus::OnHold);
+        assert_eq!(change.at, Some(120));
+        assert!(matches!(manager.try_insert(ticket(2, TTL)), Err(BookingError::QuantityExceeded)));
+    }
+
+    #[test]
+    fn test_convert_expired_hold_fails_and_expires_it() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+        manager.try_hold(ticket(1, 0), &TTL).unwrap();
+
+        let result = manager.convert_hold(&1, BookingStatus::Pending, TTL + 1);
+
+        assert!(matches!(result, Err(BookingError::HoldExpired(id)) if id == "1"));
+        assert!(manager.repository().get(&1).unwrap().unwrap().is_expired());
+    }
+
+    #[test]
+    fn test_convert_hold_rejects_invalid_targets() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+        manager.try_hold(ticket(1, 0), &TTL).unwrap();
+        manager.try_insert(ticket(2, TTL)).unwrap();
+
+        assert!(matches!(
+            manager.convert_hold(&1, BookingStatus::Completed, 60),
+            Err(BookingError::InvalidStatusTransition { .. })
+        ));
+        assert!(matches!(
+            manager.convert_hold(&2, BookingStatus::Confirmed, 60),
+            Err(BookingError::InvalidStatusTransition { from: BookingStatus::Pending, .. })
+        ));
+        assert!(matches!(
+            manager.convert_hold(&9, BookingStatus::Confirmed, 60),
+            Err(BookingError::BookingNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_sweeper_releases_overdue_holds() {
+        let clock = FakeClock::new(0_i64);
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+        manager.try_hold(ticket(1, 0), &TTL).unwrap();
+
+        clock.advance(TTL);
+        let report = ExpirySweeper::new(&clock).sweep_repository(&manager).unwrap();
+
+        assert_eq!(report.expired, vec![1]);
+        assert!(manager.repository().get(&1).unwrap().unwrap().is_expired());
+    }
+
+    #[test]
+    fn test_concurrent_holds_cannot_both_take_the_last_ticket() {
+        for _ in 0..20 {
+            let manager = Arc::new(BookingManager::new(InMemoryBookingRepository::new()));
+            let barrier = Arc::new(Barrier::new(2));
+
+            let handles: Vec<_> = (1..=2)
+                .map(|id| {
+                    let (manager, barrier) = (Arc::clone(&manager), Arc::clone(&barrier));
+                    thread::spawn(move || {
+                        barrier.wait();
+                        manager.try_hold(BookingManager::create(id, Some(id), LastTicket, 0, None, None::<()>), &TTL)
+                    })
+                })
+                .collect();
+            let results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+            assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+            assert!(results.iter().any(|result| matches!(result, Err(BookingError::QuantityExceeded))));
+            assert_eq!(manager.repository().len(), 1);
+        }
+    }
+}
@@ -14,7 +14,7 @@ mod tests {
 
     impl Bookable for MockBookable {
         fn id(&self) -> &str {
-            &self.id
+            self.id
         }
 
         fn is_available(&self) -> bool {
@@ -89,6 +89,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_cancel_sets_status_to_canceled() {
         let item = MockBookable { id: "6", available: true };
         let mut booking = BookingManager::create(1, None, item, 0, None, None::<()>);
@@ -97,10 +98,47 @@ mod tests {
     }
 
     #[test]
+    #[allow(deprecated)]
     fn test_expire_sets_status_to_expired() {
         let item = MockBookable { id: "7", available: true };
         let mut booking = BookingManager::create(1, None, item, 0, None, None::<()>);
         BookingManager::expire(&mut booking);
         assert_eq!(booking.status, BookingStatus::Expired);
     }
+
+    #[test]
+    fn test_try_cancel_rejects_completed_booking() {
+        let item = MockBookable { id: "8", available: true };
+        let mut booking = BookingManager::create(1, None, item, 0, None, None::<()>);
+        BookingManager::confirm(&mut booking).unwrap();
+        BookingManager::complete(&mut booking).unwrap();
+
+        let result = BookingManager::try_cancel(&mut booking);
+
+        assert!(matches!(
+            result,
+            Err(BookingError::InvalidStatusTransition { from: BookingStatus::Completed, to: BookingStatus::Canceled })
+        ));
+        assert_eq!(booking.status, BookingStatus::Completed);
+    }
+
+    #[test]
+    fn test_try_expire_rejects_expired_booking() {
+        let item = MockBookable { id: "9", available: true };
+        let mut booking = BookingManager::create(1, None, item, 0, None, None::<()>);
+
+        assert!(BookingManager::try_expire(&mut booking).is_ok());
+        assert!(BookingManager::try_expire(&mut booking).is_err());
+        assert_eq!(booking.status, BookingStatus::Expired);
+    }
+
+    #[test]
+    fn test_fail_pending_booking() {
+        let item = MockBookable { id: "10", available: true };
+        let mut booking = BookingManager::create(1, None, item, 0, None, None::<()>);
+
+        assert!(BookingManager::fail(&mut booking).is_ok());
+        assert_eq!(booking.status, BookingStatus::Failed);
+        assert!(BookingManager::complete(&mut booking).is_err());
+    }
 }
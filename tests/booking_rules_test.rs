@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod tests {
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::history::ChangeContext;
+    use booking_kit::model::period::BookingPeriod;
+    use booking_kit::model::status::BookingStatus;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::rules::{
+        from_fn, All, Any, Blackout, BookingRule, MaxActivePerUser, MaxAdvanceWindow, MinLeadTime, Not, RuleContext,
+        RuleFailure,
+    };
+    use booking_kit::traits::Bookable;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Room {
+        pub id: &'static str,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    type Rule = Box<dyn BookingRule<Room, u32, i64, ()>>;
+
+    const HOUR: i64 = 3_600;
+    const DAY: i64 = 24 * HOUR;
+
+    fn stay(id: u32, user: u32, room: &'static str, start: i64) -> Booking<Room, u32, i64, ()> {
+        BookingManager::create(id, Some(user), Room { id: room }, 0, None, None).with_period(start, start + DAY)
+    }
+
+    fn code(result: Result<(), RuleFailure>) -> String {
+        result.unwrap_err().code
+    }
+
+    #[test]
+    fn test_min_lead_time_and_max_advance_window() {
+        let now = 0;
+        let context = RuleContext::creation(&now);
+
+        assert_eq!(code(MinLeadTime(DAY).evaluate(&stay(1, 1, "r", HOUR), &context)), "min_lead_time");
+        assert_eq!(code(MinLeadTime(DAY).evaluate(&stay(1, 1, "r", -HOUR), &context)), "min_lead_time");
+        assert!(MinLeadTime(DAY).evaluate(&stay(1, 1, "r", DAY), &context).is_ok());
+
+        assert!(MaxAdvanceWindow(30 * DAY).evaluate(&stay(1, 1, "r", 30 * DAY), &context).is_ok());
+        assert_eq!(
+            code(MaxAdvanceWindow(30 * DAY).evaluate(&stay(1, 1, "r", 31 * DAY), &context)),
+            "max_advance_window"
+        );
+    }
+
+    #[test]
+    fn test_blackout_rejects_overlapping_period() {
+        let now = 0;
+        let rule = Blackout(vec![BookingPeriod::new(10 * DAY, 12 * DAY)]);
+        let context = RuleContext::creation(&now);
+
+        assert_eq!(code(rule.evaluate(&stay(1, 1, "r", 11 * DAY), &context)), "blackout");
+        assert!(rule.evaluate(&stay(1, 1, "r", 12 * DAY), &context).is_ok());
+    }
+
+    #[test]
+    fn test_combinators() {
+        let now = 0;
+        let context = RuleContext::creation(&now);
+        let early = stay(1, 1, "r", HOUR);
+
+        let all: All<Rule> = All(vec![Box::new(MaxAdvanceWindow(DAY)), Box::new(MinLeadTime(DAY))]);
+        assert_eq!(code(all.evaluate(&early, &context)), "min_lead_time");
+
+        let any: Any<Rule> = Any(vec![Box::new(MinLeadTime(DAY)), Box::new(MaxAdvanceWindow(DAY))]);
+        assert!(any.evaluate(&early, &context).is_ok());
+
+        let none: Any<Rule> = Any(vec![Box::new(MinLeadTime(DAY)), Box::new(Blackout(vec![BookingPeriod::new(0, DAY)]))]);
+        assert_eq!(code(none.evaluate(&early, &context)), "min_lead_time");
+        let codes: Vec<String> = none.evaluate_all(&early, &context).into_iter().map(|f| f.code).collect();
+        assert_eq!(codes, vec!["min_lead_time", "blackout"]);
+        assert!(any.evaluate_all(&early, &context).is_empty());
+
+        let empty: Any<Rule> = Any(vec![]);
+        assert_eq!(code(empty.evaluate(&early, &context)), "any");
+        assert_eq!(empty.evaluate_all(&early, &context), vec![RuleFailure::new("any", "no alternative rule passed")]);
+
+        let not = Not::new(MinLeadTime(DAY), "last_minute_only", "only last-minute bookings are allowed");
+        assert!(not.evaluate(&early, &context).is_ok());
+        assert_eq!(code(not.evaluate(&stay(1, 1, "r", 2 * DAY), &context)), "last_minute_only");
+    }
+
+    #[test]
+    fn test_manager_rejects_booking_failing_rules() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_rules(MinLeadTime(DAY));
+
+        let result = manager.try_insert(stay(1, 1, "room-1", HOUR));
+
+        match result {
+            Err(BookingError::RuleValidationFailed(message)) => assert!(message.starts_with("min_lead_time:")),
+            other => panic!("Expected RuleValidationFailed error, got {:?}", other),
+        }
+        assert!(manager.repository().is_empty());
+    }
+
+    #[test]
+    fn test_max_active_per_user_counts_stored_bookings() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_rules(MaxActivePerUser(2));
+        manager.try_insert(stay(1, 7, "room-1", DAY)).unwrap();
+        manager.try_insert(stay(2, 7, "room-2", DAY)).unwrap();
+        manager.try_insert(stay(3, 8, "room-3", DAY)).unwrap();
+
+        assert!(matches!(
+            manager.try_insert(stay(4, 7, "room-4", DAY)),
+            Err(BookingError::RuleValidationFailed(_))
+        ));
+
+        manager.cancel_booking(&1).unwrap();
+        assert!(manager.try_insert(stay(4, 7, "room-4", DAY)).is_ok());
+    }
+
+    #[test]
+    fn test_rules_are_evaluated_on_transitions() {
+        let rule = from_fn(|_: &Booking<Room, u32, i64, ()>, context: &RuleContext<'_, Room, u32, i64, ()>| {
+            match &context.transition {
+                Some(transition) if transition.to == BookingStatus::Canceled => {
+                    Err(RuleFailure::new("no_cancellation", "non-refundable booking"))
+                }
+                _ => Ok(()),
+            }
+        });
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_rules(rule);
+        manager.try_insert(stay(1, 1, "room-1", DAY)).unwrap();
+
+        assert!(matches!(manager.cancel_booking(&1), Err(BookingError::RuleValidationFailed(_))));
+        assert!(manager.transition_booking(&1, BookingStatus::Confirmed, ChangeContext::new()).is_ok());
+    }
+}
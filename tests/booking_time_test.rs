@@ -0,0 +1,99 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+    use booking_kit::clock::{Clock, FakeClock, SystemClock};
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::period::BookingPeriod;
+    use booking_kit::timestamp::BookingTime;
+    use booking_kit::traits::Bookable;
+
+    struct Room;
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            "room-1"
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    fn assert_arithmetic<Timestamp: BookingTime + std::fmt::Debug>(start: Timestamp, step: Timestamp::Duration) {
+        let later = start.checked_plus(&step).unwrap();
+
+        assert!(later > start);
+        assert_eq!(later.checked_minus(&step), Some(start.clone()));
+        assert_eq!(later.elapsed_since(&start), Some(step));
+        assert_eq!(start.elapsed_since(&later), None);
+    }
+
+    #[test]
+    fn test_unix_seconds() {
+        assert_arithmetic(1_700_000_000_i64, 3_600);
+        assert_eq!(i64::MAX.checked_plus(&1), None);
+    }
+
+    #[test]
+    fn test_system_time() {
+        assert_arithmetic(SystemTime::UNIX_EPOCH, Duration::from_secs(3_600));
+    }
+
+    #[test]
+    fn test_now_from_clock() {
+        let clock = FakeClock::new(42_i64);
+        assert_eq!(i64::now_from(&clock), 42);
+
+        let before = SystemTime::now();
+        let now: SystemTime = SystemClock.now();
+        assert!(now >= before);
+    }
+
+    #[test]
+    fn test_fake_clock_advance_by() {
+        let clock = FakeClock::new(SystemTime::UNIX_EPOCH);
+        clock.advance_by(&Duration::from_secs(90));
+
+        assert_eq!(clock.now().elapsed_since(&SystemTime::UNIX_EPOCH), Some(Duration::from_secs(90)));
+    }
+
+    #[test]
+    fn test_fake_clock_advance_saturates_on_overflow() {
+        let clock = FakeClock::new(i64::MAX - 10);
+        clock.advance(5);
+        clock.advance(60);
+
+        assert_eq!(clock.now(), i64::MAX - 5);
+    }
+
+    #[test]
+    fn test_period_and_ttl_helpers() {
+        let period = BookingPeriod::starting_at(100_i64, &50).unwrap();
+        assert_eq!(period, BookingPeriod::new(100, 150));
+        assert_eq!(period.duration(), Some(50));
+
+        let booking = BookingManager::create(1, None, Room, 100_i64, None, None::<()>).with_ttl(&900);
+        assert_eq!(booking.expires_at, Some(1_000));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono() {
+        let start = chrono::DateTime::<chrono::Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        assert_arithmetic(start, chrono::TimeDelta::hours(1));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_time() {
+        let start = time::OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+        assert_arithmetic(start, time::Duration::hours(1));
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn test_jiff() {
+        let start = jiff::Timestamp::from_second(1_700_000_000).unwrap();
+        assert_arithmetic(start, jiff::SignedDuration::from_hours(1));
+    }
+}
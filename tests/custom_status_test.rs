@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+    use booking_kit::error::BookingError;
+    use booking_kit::events::{BookingEvent, ChannelSubscriber};
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::history::ChangeContext;
+    use booking_kit::model::status::{BookingStatus, Status};
+    use booking_kit::policy::TransitionPolicy;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::traits::Bookable;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct MockBookable {
+        pub id: &'static str,
+    }
+
+    impl Bookable for MockBookable {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum VisitStatus {
+        Booked,
+        CheckedIn,
+        InProgress,
+        NoShow,
+        Completed,
+        Canceled,
+    }
+
+    impl Status for VisitStatus {
+        const ALL: &'static [Self] = &[
+            VisitStatus::Booked,
+            VisitStatus::CheckedIn,
+            VisitStatus::InProgress,
+            VisitStatus::NoShow,
+            VisitStatus::Completed,
+            VisitStatus::Canceled,
+        ];
+
+        fn is_active(&self) -> bool {
+            matches!(self, VisitStatus::Booked | VisitStatus::CheckedIn | VisitStatus::InProgress)
+        }
+
+        fn is_final(&self) -> bool {
+            !self.is_active()
+        }
+
+        fn can_transition_to(&self, next: &Self) -> bool {
+            use VisitStatus::*;
+            matches!(
+                (self, next),
+                (Booked, CheckedIn | NoShow | Canceled) | (CheckedIn, InProgress | Canceled) | (InProgress, Completed)
+            )
+        }
+
+        fn from_booking_status(status: &BookingStatus) -> Option<Self> {
+            match status {
+                BookingStatus::Pending => Some(VisitStatus::Booked),
+                BookingStatus::Canceled => Some(VisitStatus::Canceled),
+                BookingStatus::Completed => Some(VisitStatus::Completed),
+                _ => None,
+            }
+        }
+    }
+
+    type Clinic = BookingManager<InMemoryBookingRepository<MockBookable, u32, i64, (), VisitStatus>, (), (), (), (), VisitStatus>;
+
+    fn clinic() -> Clinic {
+        BookingManager::new(InMemoryBookingRepository::new()).with_policy(TransitionPolicy::default())
+    }
+
+    fn doctor() -> MockBookable {
+        MockBookable { id: "dr_jones" }
+    }
+
+    #[test]
+    fn test_manager_moves_bookings_through_custom_statuses() {
+        let manager = clinic();
+        let booking = manager.create_booking(1, Some(7), doctor(), 0, None, None).unwrap();
+        assert_eq!(booking.status, VisitStatus::Booked);
+        assert!(booking.is_pending());
+
+        manager.transition_booking(&1, VisitStatus::CheckedIn, ChangeContext::new().at(10)).unwrap();
+        manager.transition_booking(&1, VisitStatus::InProgress, ChangeContext::new().at(20)).unwrap();
+        let booking = manager.complete_booking(&1).unwrap();
+
+        assert!(booking.is_completed());
+        assert_eq!(booking.history.len(), 3);
+        assert_eq!(booking.history[0].from, VisitStatus::Booked);
+        assert_eq!(booking.history[1].to, VisitStatus::InProgress);
+        assert_eq!(booking.version, 3);
+    }
+
+    #[test]
+    fn test_disallowed_custom_transition_is_rejected() {
+        let manager = clinic();
+        manager.create_booking(1, None, doctor(), 0, None, None).unwrap();
+
+        let result = manager.transition_booking(&1, VisitStatus::Completed, ChangeContext::new());
+
+        assert!(matches!(result, Err(BookingError::InvalidStatus(_))));
+        assert_eq!(manager.repository().get(&1).unwrap().unwrap().status, VisitStatus::Booked);
+    }
+
+    #[test]
+    fn test_built_in_operations_without_a_matching_status_fail() {
+        let manager = clinic();
+        manager.create_booking(1, None, doctor(), 0, None, None).unwrap();
+
+        assert!(matches!(manager.confirm_booking(&1), Err(BookingError::InvalidStatus(_))));
+        assert!(manager.cancel_booking(&1).unwrap().is_canceled());
+    }
+
+    #[test]
+    fn test_active_and_final_classification_is_generic() {
+        let manager = clinic();
+        let first = BookingManager::create_with_status(1, None, doctor(), VisitStatus::Booked, 0, None, None::<()>).with_period(100, 200);
+        manager.try_insert(first).unwrap();
+
+        let overlapping = BookingManager::create_with_status(2, None, doctor(), VisitStatus::Booked, 0, None, None).with_period(150, 250);
+        assert!(matches!(manager.try_insert(overlapping.clone()), Err(BookingError::BookingConflict { .. })));
+
+        let no_show = manager.transition_booking(&1, VisitStatus::NoShow, ChangeContext::new()).unwrap();
+        assert!(!no_show.is_active());
+        assert!(no_show.is_final());
+        assert!(manager.try_insert(overlapping).unwrap().is_active());
+    }
+
+    #[test]
+    fn test_default_policy_is_built_from_can_transition_to() {
+        let policy = TransitionPolicy::<VisitStatus>::default();
+
+        for from in VisitStatus::ALL {
+            for to in VisitStatus::ALL {
+                assert_eq!(policy.allows(from, to), from.can_transition_to(to), "{:?} -> {:?}", from, to);
+            }
+        }
+        assert!(policy.validate(&VisitStatus::NoShow, &VisitStatus::Booked).is_err());
+    }
+
+    #[test]
+    fn test_events_carry_custom_statuses() {
+        let (sender, receiver) = mpsc::channel();
+        let manager = clinic().with_subscriber(ChannelSubscriber::from_sender(sender));
+        manager.create_booking(1, None, doctor(), 0, None, None).unwrap();
+        manager.transition_booking(&1, VisitStatus::CheckedIn, ChangeContext::new()).unwrap();
+        manager.cancel_booking(&1).unwrap();
+
+        let events: Vec<BookingEvent<u32, i64, VisitStatus>> = receiver.try_iter().collect();
+        assert!(matches!(&events[0], BookingEvent::Created(data) if data.to == VisitStatus::Booked));
+        assert!(matches!(&events[1], BookingEvent::Modified(data) if data.to == VisitStatus::CheckedIn));
+        assert!(matches!(&events[2], BookingEvent::Canceled(data) if data.from == Some(VisitStatus::CheckedIn)));
+    }
+}
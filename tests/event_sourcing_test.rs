@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod tests {
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::history::ChangeContext;
+    use booking_kit::model::status::{BookingStatus, Status};
+    use booking_kit::sourcing::{BookingAggregate, BookingLogEvent};
+    use booking_kit::traits::Bookable;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Room {
+        pub id: String,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    type Log = Vec<BookingLogEvent<Room, u32, i64, ()>>;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum VisitStatus {
+        Booked,
+        Seen,
+        Canceled,
+    }
+
+    impl Status for VisitStatus {
+        const ALL: &'static [Self] = &[VisitStatus::Booked, VisitStatus::Seen, VisitStatus::Canceled];
+
+        fn is_active(&self) -> bool {
+            matches!(self, VisitStatus::Booked)
+        }
+
+        fn is_final(&self) -> bool {
+            !self.is_active()
+        }
+
+        fn can_transition_to(&self, next: &Self) -> bool {
+            matches!((self, next), (VisitStatus::Booked, VisitStatus::Seen | VisitStatus::Canceled))
+        }
+
+        fn from_booking_status(status: &BookingStatus) -> Option<Self> {
+            match status {
+                BookingStatus::Pending => Some(VisitStatus::Booked),
+                BookingStatus::Canceled => Some(VisitStatus::Canceled),
+                _ => None,
+            }
+        }
+    }
+
+    fn booking() -> Booking<Room, u32, i64, ()> {
+        BookingManager::create(1, Some(7), Room { id: "room-1".into() }, 0, None, None).with_period(100, 200)
+    }
+
+    fn confirmed_then_completed() -> Log {
+        let mut aggregate = BookingAggregate::new();
+        aggregate.create(booking()).unwrap();
+        aggregate.transition(BookingStatus::Confirmed, ChangeContext::new().at(10).by(7)).unwrap();
+        aggregate.transition(BookingStatus::Completed, ChangeContext::new().at(200)).unwrap();
+        aggregate.events().to_vec()
+    }
+
+    #[test]
+    fn test_replay_matches_manager_transitions() {
+        let mut expected = booking();
+        BookingManager::transition_with(&Default::default(), &mut expected, BookingStatus::Confirmed, ChangeContext::new().at(10).by(7)).unwrap();
+        BookingManager::transition_with(&Default::default(), &mut expected, BookingStatus::Completed, ChangeContext::new().at(200)).unwrap();
+
+        let aggregate = BookingAggregate::replay(confirmed_then_completed()).unwrap();
+
+        assert_eq!(aggregate.state(), Some(&expected));
+        assert_eq!(aggregate.version(), 3);
+    }
+
+    #[test]
+    fn test_invalid_transitions_are_rejected() {
+        let mut aggregate = BookingAggregate::new();
+        aggregate.create(booking()).unwrap();
+        aggregate.transition(BookingStatus::Canceled, ChangeContext::new()).unwrap();
+
+        let result = aggregate.transition(BookingStatus::Confirmed, ChangeContext::new());
+
+        assert!(matches!(result, Err(BookingError::InvalidStatusTransition { from: BookingStatus::Canceled, .. })));
+        assert_eq!(aggregate.version(), 2);
+
+        // A stored transition must start from the status the booking actually has.
+        let mut log = confirmed_then_completed();
+        log.remove(1);
+        assert!(matches!(
+            BookingAggregate::replay(log),
+            Err(BookingError::InvalidStatusTransition { from: BookingStatus::Pending, to: BookingStatus::Completed })
+        ));
+    }
+
+    #[test]
+    fn test_log_must_start_with_a_single_creation() {
+        let mut log = confirmed_then_completed();
+        log.remove(0);
+        assert!(matches!(BookingAggregate::replay(log), Err(BookingError::GeneralError(_))));
+
+        let mut aggregate = BookingAggregate::new();
+        aggregate.create(booking()).unwrap();
+        assert!(matches!(aggregate.create(booking()), Err(BookingError::GeneralError(_))));
+    }
+
+    #[test]
+    fn test_replay_from_snapshot_matches_full_replay() {
+        let log = confirmed_then_completed();
+        let mut aggregate = BookingAggregate::replay(log[..2].to_vec()).unwrap();
+        let snapshot = aggregate.snapshot().unwrap();
+        assert_eq!(snapshot.version, 2);
+
+        aggregate.transition(BookingStatus::Completed, ChangeContext::new().at(200)).unwrap();
+        let resumed = BookingAggregate::replay_from(snapshot, log[2..].to_vec()).unwrap();
+
+        assert_eq!(resumed.version(), 3);
+        assert_eq!(resumed.events().len(), 1);
+        assert_eq!(resumed.state(), BookingAggregate::replay(log).unwrap().state());
+    }
+
+    #[test]
+    fn test_state_at_any_point() {
+        let log = confirmed_then_completed();
+
+        assert_eq!(BookingAggregate::state_at(log.clone(), 0).unwrap(), None);
+        let status_at = |version| BookingAggregate::state_at(log.clone(), version).unwrap().unwrap().status;
+        assert_eq!(status_at(1), BookingStatus::Pending);
+        assert_eq!(status_at(2), BookingStatus::Confirmed);
+        assert_eq!(status_at(9), BookingStatus::Completed);
+
+        let status_as_of = |at| BookingAggregate::state_as_of(log.clone(), &at).unwrap().map(|b| b.status);
+        assert_eq!(status_as_of(-1), None);
+        assert_eq!(status_as_of(9), Some(BookingStatus::Pending));
+        assert_eq!(status_as_of(150), Some(BookingStatus::Confirmed));
+        assert_eq!(status_as_of(200), Some(BookingStatus::Completed));
+    }
+
+    #[test]
+    fn test_log_serde_round_trip() {
+        let log = confirmed_then_completed();
+
+        let json = serde_json::to_string(&log).unwrap();
+        let restored: Log = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, log);
+    }
+
+    #[test]
+    fn test_created_booking_must_be_initial_and_untransitioned() {
+        for status in [BookingStatus::Pending, BookingStatus::OnHold, BookingStatus::Waitlisted] {
+            let mut created = booking();
+            created.status = status;
+            assert!(BookingAggregate::new().create(created).is_ok());
+        }
+
+        let mut confirmed = booking();
+        confirmed.status = BookingStatus::Confirmed;
+        let mut aggregate = BookingAggregate::new();
+        assert!(matches!(aggregate.create(confirmed), Err(BookingError::InvalidStatus(_))));
+        assert_eq!(aggregate.version(), 0);
+
+        let mut transitioned = booking();
+        BookingManager::transition_with(&Default::default(), &mut transitioned, BookingStatus::Canceled, ChangeContext::new()).unwrap();
+        transitioned.status = BookingStatus::Pending;
+        assert!(matches!(aggregate.create(transitioned), Err(BookingError::GeneralError(_))));
+        assert!(aggregate.state().is_none());
+    }
+
+    #[test]
+    fn test_booking_version_advances_with_every_event() {
+        let mut aggregate = BookingAggregate::new();
+        aggregate.create(booking()).unwrap();
+        assert_eq!(aggregate.state().unwrap().version, 0);
+
+        for (status, version) in [(BookingStatus::Confirmed, 1), (BookingStatus::CheckedIn, 2), (BookingStatus::Completed, 3)] {
+            aggregate.transition(status, ChangeContext::new()).unwrap();
+            assert_eq!(aggregate.state().unwrap().version, version);
+            assert_eq!(aggregate.version(), version + 1);
+        }
+        let resumed = BookingAggregate::replay_from(aggregate.snapshot().unwrap(), Vec::new()).unwrap();
+        assert_eq!(resumed.state().unwrap().version, 3);
+    }
+
+    #[test]
+    fn test_custom_statuses_are_replayed() {
+        let visit = BookingManager::create_with_status(1, None, Room { id: "dr-jones".into() }, VisitStatus::Booked, 0_i64, None, None::<()>);
+        let mut aggregate = BookingAggregate::new();
+        aggregate.create(visit.clone()).unwrap();
+        aggregate.transition(VisitStatus::Seen, ChangeContext::new().at(10)).unwrap();
+
+        assert!(matches!(aggregate.transition(VisitStatus::Canceled, ChangeContext::new()), Err(BookingError::InvalidStatus(_))));
+        let replayed = BookingAggregate::replay(aggregate.events().to_vec()).unwrap();
+        assert_eq!(replayed.state().unwrap().status, VisitStatus::Seen);
+        assert_eq!(replayed.state().unwrap().version, 1);
+
+        let mut seen = visit;
+        seen.status = VisitStatus::Seen;
+        assert!(matches!(BookingAggregate::new().create(seen), Err(BookingError::InvalidStatus(_))));
+    }
+}
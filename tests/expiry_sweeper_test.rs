@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+    use booking_kit::clock::{Clock, FakeClock};
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::status::BookingStatus;
+    use booking_kit::policy::TransitionPolicy;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::sweeper::{ExpirySweeper, EXPIRY_REASON};
+    use booking_kit::traits::Bookable;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Room {
+        pub id: &'static str,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    const HOLD: i64 = 15 * 60;
+
+    #[test]
+    fn test_sweep_expires_only_overdue_pending_bookings() {
+        let clock = FakeClock::new(0_i64);
+        let mut bookings = vec![
+            BookingManager::create(1, None, Room { id: "room-1" }, 0, Some(HOLD), None::<()>),
+            BookingManager::create(2, None, Room { id: "room-2" }, 0, Some(HOLD), None::<()>),
+            BookingManager::create(3, None, Room { id: "room-3" }, 0, Some(2 * HOLD), None::<()>),
+            BookingManager::create(4, None, Room { id: "room-4" }, 0, None, None::<()>),
+        ];
+        BookingManager::confirm(&mut bookings[1]).unwrap();
+
+        let sweeper = ExpirySweeper::new(&clock);
+        assert!(sweeper.sweep(&mut bookings).is_empty());
+
+        clock.advance(HOLD);
+        let report = sweeper.sweep(&mut bookings);
+
+        assert_eq!(report.swept_at, HOLD);
+        assert_eq!(report.expired, vec![1]);
+        assert_eq!(bookings[0].status, BookingStatus::Expired);
+        assert_eq!(bookings[1].status, BookingStatus::Confirmed);
+        assert_eq!(bookings[2].status, BookingStatus::Pending);
+        assert_eq!(bookings[3].status, BookingStatus::Pending);
+
+        let change = bookings[0].last_change().unwrap();
+        assert_eq!(change.at, Some(HOLD));
+        assert_eq!(change.reason.as_deref(), Some(EXPIRY_REASON));
+    }
+
+    #[test]
+    fn test_sweep_repository_persists_expiry() {
+        let clock = FakeClock::new(0_i64);
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+        manager.create_booking(1, None, Room { id: "room-1" }, 0, Some(HOLD), None::<()>).unwrap();
+        manager.create_booking(2, None, Room { id: "room-2" }, 0, Some(2 * HOLD), None::<()>).unwrap();
+
+        clock.set(HOLD + 1);
+        let report = ExpirySweeper::new(&clock).sweep_repository(&manager).unwrap();
+
+        assert_eq!(report.expired, vec![1]);
+        let repo = manager.repository();
+        assert_eq!(repo.get(&1).unwrap().unwrap().status, BookingStatus::Expired);
+        assert_eq!(repo.get(&2).unwrap().unwrap().status, BookingStatus::Pending);
+    }
+
+    #[test]
+    fn test_sweep_repository_reports_forbidden_expiry() {
+        let clock = FakeClock::new(HOLD);
+        let policy = TransitionPolicy::default().forbid(BookingStatus::Pending, BookingStatus::Expired);
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_policy(policy);
+        manager.create_booking(1, None, Room { id: "room-1" }, 0, Some(HOLD), None::<()>).unwrap();
+
+        let report = ExpirySweeper::new(&clock).sweep_repository(&manager).unwrap();
+
+        assert!(report.expired.is_empty());
+        assert!(!report.is_empty());
+        assert!(matches!(report.skipped[..], [(1, BookingError::InvalidStatusTransition { .. })]));
+    }
+
+    #[test]
+    fn test_fake_clock_with_system_time() {
+        let start = SystemTime::UNIX_EPOCH;
+        let clock = FakeClock::new(start);
+        clock.advance(Duration::from_secs(60));
+
+        assert_eq!(clock.now(), start + Duration::from_secs(60));
+    }
+}
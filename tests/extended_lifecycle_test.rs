@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::status::{BookingStatus, Status};
+    use booking_kit::model::typestate::TypedBooking;
+    use booking_kit::policy::TransitionPolicy;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::traits::Bookable;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Room {
+        pub id: &'static str,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    type Repo = InMemoryBookingRepository<Room, u32, i64, ()>;
+
+    fn stay(id: u32, start: i64, end: i64) -> Booking<Room, u32, i64, ()> {
+        BookingManager::create(id, None, Room { id: "room-1" }, 0, None, None).with_period(start, end)
+    }
+
+    #[test]
+    fn test_checked_in_booking_holds_item_until_completed() {
+        let manager = BookingManager::new(Repo::new());
+        manager.try_insert(stay(1, 10, 20)).unwrap();
+        manager.confirm_booking(&1).unwrap();
+
+        let checked_in = manager.check_in_booking(&1).unwrap();
+        assert!(checked_in.is_checked_in());
+        assert!(checked_in.is_active());
+        assert!(matches!(manager.try_insert(stay(2, 15, 25)), Err(BookingError::BookingConflict { .. })));
+
+        let completed = manager.complete_booking(&1).unwrap();
+        assert!(completed.is_completed());
+        assert_eq!(completed.history[1].from, BookingStatus::Confirmed);
+        assert_eq!(completed.history[2].from, BookingStatus::CheckedIn);
+    }
+
+    #[test]
+    fn test_no_show_releases_item() {
+        let manager = BookingManager::new(Repo::new());
+        manager.try_insert(stay(1, 10, 20)).unwrap();
+        manager.confirm_booking(&1).unwrap();
+
+        let no_show = manager.mark_no_show_booking(&1).unwrap();
+        assert!(no_show.is_no_show());
+        assert!(no_show.is_final());
+        assert!(!no_show.is_active());
+        assert!(manager.try_insert(stay(2, 15, 25)).is_ok());
+        assert!(matches!(manager.check_in_booking(&1), Err(BookingError::InvalidStatusTransition { .. })));
+    }
+
+    #[test]
+    fn test_only_confirmed_bookings_are_checked_in_or_refunded() {
+        let mut booking = stay(1, 10, 20);
+
+        assert!(matches!(BookingManager::check_in(&mut booking), Err(BookingError::InvalidStatusTransition { .. })));
+        assert!(matches!(BookingManager::refund(&mut booking), Err(BookingError::InvalidStatusTransition { .. })));
+        assert!(booking.is_pending());
+
+        BookingManager::confirm(&mut booking).unwrap();
+        BookingManager::refund(&mut booking).unwrap();
+        assert!(booking.is_refunded());
+        assert!(booking.is_final());
+    }
+
+    #[test]
+    fn test_default_policy_covers_extended_statuses() {
+        let policy = TransitionPolicy::default();
+
+        for from in BookingStatus::ALL {
+            for to in BookingStatus::ALL {
+                assert_eq!(policy.allows(from, to), from.can_transition_to(to), "{:?} -> {:?}", from, to);
+            }
+        }
+        assert!(policy.allows(&BookingStatus::Confirmed, &BookingStatus::NoShow));
+        assert!(!policy.allows(&BookingStatus::CheckedIn, &BookingStatus::Canceled));
+    }
+
+    #[test]
+    fn test_typed_booking_checks_in() {
+        let booking = TypedBooking::create(1, None, Room { id: "room-1" }, 0_i64, None, None::<()>)
+            .confirm()
+            .check_in()
+            .complete();
+
+        assert_eq!(booking.status(), BookingStatus::Completed);
+        assert_eq!(booking.history[1].to, BookingStatus::CheckedIn);
+    }
+}
@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+    use std::path::PathBuf;
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::status::BookingStatus;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::file::{FileBookingRepository, TruncatedTail};
+    use booking_kit::traits::Bookable;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Room {
+        pub id: String,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    type Repository = FileBookingRepository<Room, u32, i64, ()>;
+
+    /// Log and snapshot paths for one test, removed on drop.
+    struct TempLog(PathBuf);
+
+    impl TempLog {
+        fn new(name: &str) -> Self {
+            let log = TempLog(std::env::temp_dir().join(format!("booking_kit_file_{}_{}.jsonl", name, std::process::id())));
+            log.clean();
+            log
+        }
+
+        fn snapshot(&self) -> PathBuf {
+            PathBuf::from(format!("{}.snapshot", self.0.display()))
+        }
+
+        fn clean(&self) {
+            let _ = fs::remove_file(&self.0);
+            let _ = fs::remove_file(self.snapshot());
+        }
+    }
+
+    impl Drop for TempLog {
+        fn drop(&mut self) {
+            self.clean();
+        }
+    }
+
+    fn room(booking_id: u32, room_id: &str) -> Booking<Room, u32, i64, ()> {
+        BookingManager::create(booking_id, Some(7), Room { id: room_id.into() }, 0, None, None)
+    }
+
+    #[test]
+    fn test_every_mutation_is_appended_as_a_line() {
+        let log = TempLog::new("append");
+        let manager = BookingManager::new(Repository::open(&log.0).unwrap());
+
+        manager.try_insert(room(1, "room-1")).unwrap();
+        manager.confirm_booking(&1).unwrap();
+        manager.repository().remove(&1).unwrap();
+
+        let content = fs::read_to_string(&log.0).unwrap();
+        let ops: Vec<_> = content
+            .lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap()["op"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(ops, vec!["insert", "update", "remove"]);
+    }
+
+    #[test]
+    fn test_reopening_rebuilds_index() {
+        let log = TempLog::new("reopen");
+        {
+            let manager = BookingManager::new(Repository::open(&log.0).unwrap());
+            manager.try_insert(room(1, "room-1")).unwrap();
+            manager.try_insert(room(2, "room-2")).unwrap();
+            manager.try_insert(room(3, "room-3")).unwrap();
+            manager.cancel_booking(&2).unwrap();
+            manager.repository().remove(&3).unwrap();
+        }
+
+        let repository = Repository::open(&log.0).unwrap();
+
+        assert!(repository.truncated_tail().is_none());
+        assert_eq!(repository.list_by_user(&7).unwrap().iter().map(|b| b.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(repository.get(&2).unwrap().unwrap().status, BookingStatus::Canceled);
+        assert_eq!(repository.get(&2).unwrap().unwrap().version, 1);
+        assert!(repository.get(&3).unwrap().is_none());
+        assert!(matches!(repository.insert(room(1, "room-9")), Err(BookingError::DuplicateBooking(_))));
+    }
+
+    #[test]
+    fn test_compaction_moves_bookings_into_snapshot() {
+        let log = TempLog::new("compact");
+        let repository = Repository::open(&log.0).unwrap();
+        let manager = BookingManager::new(repository);
+        for booking_id in 1..=3 {
+            manager.try_insert(room(booking_id, &format!("room-{booking_id}"))).unwrap();
+            manager.confirm_booking(&booking_id).unwrap();
+        }
+        manager.repository().remove(&2).unwrap();
+        assert!(manager.repository().log_len().unwrap() > 0);
+
+        manager.repository().compact().unwrap();
+
+        assert_eq!(manager.repository().log_len().unwrap(), 0);
+        assert_eq!(fs::read_to_string(log.snapshot()).unwrap().lines().count(), 2);
+        manager.complete_booking(&3).unwrap();
+        drop(manager);
+
+        let reopened = Repository::open(&log.0).unwrap();
+        let stored = reopened.list_by_user(&7).unwrap();
+        assert_eq!(stored.iter().map(|b| b.id).collect::<Vec<_>>(), vec![1, 3]);
+        assert!(stored[0].is_confirmed());
+        assert_eq!(stored[1].status, BookingStatus::Completed);
+    }
+
+    #[test]
+    fn test_log_replayed_over_snapshot_is_idempotent() {
+        let log = TempLog::new("idempotent");
+        let manager = BookingManager::new(Repository::open(&log.0).unwrap());
+        manager.try_insert(room(1, "room-1")).unwrap();
+        manager.confirm_booking(&1).unwrap();
+        let before = fs::read(&log.0).unwrap();
+        manager.repository().compact().unwrap();
+        drop(manager);
+
+        // Simulates a crash after the snapshot was renamed but before the log was emptied.
+        fs::write(&log.0, before).unwrap();
+        let repository = Repository::open(&log.0).unwrap();
+
+        assert_eq!(repository.list_by_item("room-1").unwrap().len(), 1);
+        assert!(repository.get(&1).unwrap().unwrap().is_confirmed());
+    }
+
+    #[test]
+    fn test_truncated_trailing_line_is_dropped_and_reported() {
+        let log = TempLog::new("truncated");
+        {
+            let repository = Repository::open(&log.0).unwrap();
+            repository.insert(room(1, "room-1")).unwrap();
+            repository.insert(room(2, "room-2")).unwrap();
+        }
+        let complete = fs::metadata(&log.0).unwrap().len();
+        let partial = r#"{"op":"insert","booking":{"id":3,"user_id":7,"item":{"id":"ro"#;
+        OpenOptions::new().append(true).open(&log.0).unwrap().write_all(partial.as_bytes()).unwrap();
+
+        let repository = Repository::open(&log.0).unwrap();
+
+        assert_eq!(
+            repository.truncated_tail(),
+            Some(&TruncatedTail { line: 3, offset: complete, len: partial.len() })
+        );
+        assert_eq!(fs::metadata(&log.0).unwrap().len(), complete);
+        assert!(repository.get(&3).unwrap().is_none());
+
+        // New records start on a line of their own.
+        repository.insert(room(3, "room-3")).unwrap();
+        drop(repository);
+        let reopened = Repository::open(&log.0).unwrap();
+        assert!(reopened.truncated_tail().is_none());
+        assert_eq!(reopened.list_by_user(&7).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_corrupt_complete_line_fails_to_open() {
+        let log = TempLog::new("corrupt");
+        {
+            let repository = Repository::open(&log.0).unwrap();
+            repository.insert(room(1, "room-1")).unwrap();
+        }
+        OpenOptions::new().append(true).open(&log.0).unwrap().write_all(b"not json\n").unwrap();
+
+        let result = Repository::open(&log.0);
+
+        assert!(matches!(result, Err(BookingError::StorageError(message)) if message.contains("line 2")));
+    }
+
+    #[test]
+    fn test_stale_update_is_rejected() {
+        let log = TempLog::new("stale");
+        let repository = Repository::open(&log.0).unwrap();
+        repository.insert(room(1, "room-1")).unwrap();
+
+        let mut stale = repository.get(&1).unwrap().unwrap();
+        stale.version = 3;
+
+        assert!(matches!(repository.update(stale), Err(BookingError::ConcurrentModification { stored: 0, .. })));
+        assert_eq!(fs::read_to_string(&log.0).unwrap().lines().count(), 1);
+    }
+}
@@ -0,0 +1,152 @@
+// Makes writes fail by lowering the file size limit of the process, so these tests live in their
+// own binary and take `LIMIT` to run one at a time.
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use std::ffi::{c_int, c_ulong};
+    use std::fs;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Mutex, MutexGuard};
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::outbox::BookingOutbox;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::file::FileBookingRepository;
+    use booking_kit::repository::file_outbox::FileOutboxRepository;
+    use booking_kit::traits::Bookable;
+    use serde::{Deserialize, Serialize};
+
+    const RLIMIT_FSIZE: c_int = 1;
+    const SIGXFSZ: c_int = 25;
+    const SIG_IGN: usize = 1;
+
+    #[repr(C)]
+    struct RLimit {
+        current: c_ulong,
+        max: c_ulong,
+    }
+
+    unsafe extern "C" {
+        fn getrlimit(resource: c_int, limit: *mut RLimit) -> c_int;
+        fn setrlimit(resource: c_int, limit: *const RLimit) -> c_int;
+        fn signal(signal: c_int, handler: usize) -> usize;
+    }
+
+    static LIMIT: Mutex<()> = Mutex::new(());
+
+    /// Fails writes past `len` bytes of any file until dropped.
+    struct FileSizeLimit {
+        previous: c_ulong,
+        _lock: MutexGuard<'static, ()>,
+    }
+
+    impl FileSizeLimit {
+        fn new(len: u64) -> Self {
+            let lock = LIMIT.lock().unwrap_or_else(|e| e.into_inner());
+            let mut limit = RLimit { current: 0, max: 0 };
+            // SAFETY: plain libc calls on valid pointers; ignoring SIGXFSZ turns writes past the
+            // limit into `EFBIG` errors instead of killing the process.
+            unsafe {
+                signal(SIGXFSZ, SIG_IGN);
+                assert_eq!(getrlimit(RLIMIT_FSIZE, &mut limit), 0);
+                let previous = limit.current;
+                limit.current = len as c_ulong;
+                assert_eq!(setrlimit(RLIMIT_FSIZE, &limit), 0);
+                Self { previous, _lock: lock }
+            }
+        }
+    }
+
+    impl Drop for FileSizeLimit {
+        fn drop(&mut self) {
+            let mut limit = RLimit { current: 0, max: 0 };
+            // SAFETY: see `FileSizeLimit::new`.
+            unsafe {
+                getrlimit(RLIMIT_FSIZE, &mut limit);
+                limit.current = self.previous;
+                setrlimit(RLIMIT_FSIZE, &limit);
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Room {
+        pub id: String,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("booking_kit_write_failure_{}_{}.jsonl", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn room(booking_id: u32, room_id: &str) -> Booking<Room, u32, i64, ()> {
+        BookingManager::create(booking_id, Some(7), Room { id: room_id.into() }, 0, None, None)
+    }
+
+    fn len(path: &Path) -> u64 {
+        fs::metadata(path).unwrap().len()
+    }
+
+    #[test]
+    fn test_failed_append_is_rolled_back() {
+        let path = temp_path("repository");
+        let repository = FileBookingRepository::<Room, u32, i64, ()>::open(&path).unwrap();
+        repository.insert(room(1, "room-1")).unwrap();
+        let before = len(&path);
+
+        {
+            // Leaves room for part of the record only.
+            let _limit = FileSizeLimit::new(before + 10);
+            assert!(matches!(repository.insert(room(2, "room-2")), Err(BookingError::StorageError(_))));
+        }
+
+        assert_eq!(len(&path), before);
+        assert!(repository.get(&2).unwrap().is_none());
+        repository.insert(room(3, "room-3")).unwrap();
+        drop(repository);
+
+        let reopened = FileBookingRepository::<Room, u32, i64, ()>::open(&path).unwrap();
+        assert!(reopened.truncated_tail().is_none());
+        assert_eq!(reopened.list_by_user(&7).unwrap().iter().map(|b| b.id).collect::<Vec<_>>(), vec![1, 3]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_failed_outbox_write_stores_neither_booking_nor_event() {
+        let path = temp_path("outbox");
+        let manager = BookingManager::new(FileOutboxRepository::<Room, u32, i64, ()>::open(&path).unwrap());
+        manager.try_insert(room(1, "room-1")).unwrap();
+        let before = len(&path);
+
+        {
+            let _limit = FileSizeLimit::new(before + 10);
+            assert!(matches!(manager.confirm_booking(&1), Err(BookingError::StorageError(_))));
+            assert!(matches!(manager.try_insert(room(2, "room-2")), Err(BookingError::StorageError(_))));
+            assert!(matches!(manager.repository().acknowledge(1), Err(BookingError::StorageError(_))));
+        }
+
+        assert_eq!(len(&path), before);
+        assert!(manager.repository().get(&1).unwrap().unwrap().is_pending());
+        assert!(manager.repository().get(&2).unwrap().is_none());
+        assert_eq!(manager.repository().pending_messages(10).unwrap().len(), 1);
+        manager.confirm_booking(&1).unwrap();
+        drop(manager);
+
+        let reopened = FileOutboxRepository::<Room, u32, i64, ()>::open(&path).unwrap();
+        assert!(reopened.truncated_tail().is_none());
+        assert!(reopened.get(&1).unwrap().unwrap().is_confirmed());
+        assert_eq!(reopened.pending_messages(10).unwrap().iter().map(|m| m.id).collect::<Vec<_>>(), vec![1, 2]);
+        fs::remove_file(path).unwrap();
+    }
+}
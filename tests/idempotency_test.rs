@@ -0,0 +1,178 @@
+#[cfg(test)]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+    use std::sync::Arc;
+    use std::thread;
+    use booking_kit::error::BookingError;
+    use booking_kit::events::{from_fn, BookingEvent};
+    use booking_kit::idempotency::{IdempotencyStore, InMemoryIdempotencyStore};
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::traits::Bookable;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Room {
+        pub id: &'static str,
+        pub available: bool,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            self.available
+        }
+    }
+
+    /// A room whose availability check panics once, e.g. on a lost connection to an inventory
+    /// service.
+    #[derive(Debug, Clone)]
+    struct FlakyRoom {
+        pub panic_once: Arc<AtomicBool>,
+    }
+
+    impl PartialEq for FlakyRoom {
+        fn eq(&self, _other: &Self) -> bool {
+            true
+        }
+    }
+
+    impl Bookable for FlakyRoom {
+        fn id(&self) -> &str {
+            "room-1"
+        }
+
+        fn is_available(&self) -> bool {
+            if self.panic_once.swap(false, Ordering::SeqCst) {
+                panic!("inventory service unreachable");
+            }
+            true
+        }
+    }
+
+    type Repo = InMemoryBookingRepository<Room, u32, i64, ()>;
+    type Store = InMemoryIdempotencyStore<u32, Booking<Room, u32, i64, ()>>;
+
+    fn night(booking_id: u32, room_id: &'static str) -> Booking<Room, u32, i64, ()> {
+        BookingManager::create(booking_id, Some(7), Room { id: room_id, available: true }, 0, None, None).with_period(100, 200)
+    }
+
+    #[test]
+    fn test_retry_returns_original_booking_once() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&created);
+        let manager = BookingManager::new(Repo::new())
+            .with_subscriber(from_fn(move |event: &BookingEvent<u32, i64>| {
+                if matches!(event, BookingEvent::Created(_)) {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+            }))
+            .with_idempotency(Store::new());
+
+        let first = manager.try_insert_idempotent("key-1", night(1, "room-1")).unwrap();
+        let retried = manager.try_insert_idempotent("key-1", night(1, "room-1")).unwrap();
+
+        assert_eq!(retried, first);
+        assert_eq!(manager.repository().len(), 1);
+        assert_eq!(created.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_replay_returns_current_state_of_booking() {
+        let manager = BookingManager::new(Repo::new()).with_idempotency(Store::new());
+        manager.try_insert_idempotent("key-1", night(1, "room-1")).unwrap();
+        manager.confirm_booking(&1).unwrap();
+
+        let retried = manager.try_insert_idempotent("key-1", night(1, "room-1")).unwrap();
+
+        assert!(retried.is_confirmed());
+    }
+
+    #[test]
+    fn test_replay_returns_original_error() {
+        let manager = BookingManager::new(Repo::new()).with_idempotency(Store::new());
+        manager.try_insert(night(1, "room-1")).unwrap();
+
+        let first = manager.try_insert_idempotent("key-2", night(2, "room-1"));
+        assert!(matches!(first, Err(BookingError::BookingConflict { .. })));
+
+        // Even once the room is free again, the retry gets the original answer.
+        manager.cancel_booking(&1).unwrap();
+        let retried = manager.try_insert_idempotent("key-2", night(2, "room-1"));
+        assert!(matches!(retried, Err(BookingError::BookingConflict { ref booking_id, .. }) if booking_id == "1"));
+    }
+
+    #[test]
+    fn test_key_reused_with_different_payload_is_rejected() {
+        let manager = BookingManager::new(Repo::new()).with_idempotency(Store::new());
+        manager.try_insert_idempotent("key-1", night(1, "room-1")).unwrap();
+
+        let result = manager.try_insert_idempotent("key-1", night(1, "room-2"));
+
+        assert!(matches!(result, Err(BookingError::IdempotencyKeyReused(ref key)) if key == "key-1"));
+        assert_eq!(result.unwrap_err().code(), "idempotency_key_reused");
+        assert_eq!(manager.repository().len(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_retries_create_one_booking() {
+        let manager = Arc::new(BookingManager::new(Repo::new()).with_idempotency(Store::new()));
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let manager = Arc::clone(&manager);
+                thread::spawn(move || manager.try_insert_idempotent("key-1", night(1, "room-1")))
+            })
+            .collect();
+
+        for handle in handles {
+            match handle.join().unwrap() {
+                Ok(booking) => assert_eq!(booking.id, 1),
+                Err(e) => assert!(matches!(e, BookingError::IdempotencyKeyInUse(_))),
+            }
+        }
+        assert_eq!(manager.try_insert_idempotent("key-1", night(1, "room-1")).unwrap().id, 1);
+        assert_eq!(manager.repository().len(), 1);
+        assert_eq!(manager.idempotency().len(), 1);
+    }
+
+    #[test]
+    fn test_store_keeps_first_record_and_forgets() {
+        let manager = BookingManager::new(Repo::new()).with_idempotency(Store::new());
+        manager.try_insert_idempotent("key-1", night(1, "room-1")).unwrap();
+
+        let record = manager.idempotency().get("key-1").unwrap().unwrap();
+        assert_eq!(record.outcome.unwrap().unwrap(), 1);
+        assert!(manager.idempotency().claim("key-1", &night(9, "room-9")).unwrap().is_some());
+        assert!(manager.idempotency().forget("key-1").unwrap());
+        assert!(manager.idempotency().is_empty());
+    }
+
+    #[test]
+    fn test_panic_while_storing_releases_key() {
+        let manager = BookingManager::new(InMemoryBookingRepository::<FlakyRoom, u32, i64, ()>::new())
+            .with_idempotency(InMemoryIdempotencyStore::new());
+        let booking = BookingManager::create(1, Some(7), FlakyRoom { panic_once: Arc::new(AtomicBool::new(true)) }, 0, None, None);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| manager.try_insert_idempotent("key-1", booking.clone())));
+
+        assert!(result.is_err());
+        assert!(manager.idempotency().get("key-1").unwrap().is_none());
+        assert_eq!(manager.try_insert_idempotent("key-1", booking).unwrap().id, 1);
+        assert_eq!(manager.repository().len(), 1);
+    }
+
+    #[test]
+    fn test_manager_without_store_fails() {
+        let manager = BookingManager::new(Repo::new());
+
+        let result = manager.try_insert_idempotent("key-1", night(1, "room-1"));
+
+        assert!(matches!(result, Err(BookingError::CreationFailed(_))));
+        assert!(manager.repository().is_empty());
+    }
+}
@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::multi::{MultiBooking, MultiBookingStatus};
+    use booking_kit::model::status::BookingStatus;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::traits::Bookable;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum TripItem {
+        Flight { id: String, seats: u32 },
+        Hotel { id: String, open: bool },
+        Car { id: String },
+    }
+
+    impl Bookable for TripItem {
+        fn id(&self) -> &str {
+            match self {
+                TripItem::Flight { id, .. } | TripItem::Hotel { id, .. } | TripItem::Car { id } => id,
+            }
+        }
+
+        fn is_available(&self) -> bool {
+            match self {
+                TripItem::Flight { seats, .. } => *seats > 0,
+                TripItem::Hotel { open, .. } => *open,
+                TripItem::Car { .. } => true,
+            }
+        }
+
+        fn capacity(&self) -> Option<u32> {
+            match self {
+                TripItem::Flight { seats, .. } => Some(*seats),
+                _ => None,
+            }
+        }
+    }
+
+    type Line = Booking<TripItem, u32, i64, ()>;
+
+    fn flight(line_id: u32, seats: u32) -> Line {
+        let item = TripItem::Flight { id: "GA123".into(), seats };
+        BookingManager::create(line_id, Some(7), item, 0, None, None).with_quantity(2)
+    }
+
+    fn hotel(line_id: u32, id: &str, open: bool) -> Line {
+        let item = TripItem::Hotel { id: id.into(), open };
+        BookingManager::create(line_id, Some(7), item, 0, None, None).with_period(10, 20)
+    }
+
+    fn car(line_id: u32) -> Line {
+        BookingManager::create(line_id, Some(7), TripItem::Car { id: "car-1".into() }, 0, None, None).with_period(10, 20)
+    }
+
+    #[test]
+    fn test_try_create_multi_reports_every_unavailable_item() {
+        let lines = vec![flight(1, 0), hotel(2, "hotel-1", false), car(3)];
+
+        let result = BookingManager::try_create_multi(100, Some(7), lines);
+
+        assert!(matches!(result, Err(BookingError::ItemUnavailable(ids)) if ids == "GA123, hotel-1"));
+    }
+
+    #[test]
+    fn test_try_create_multi_rejects_conflicting_lines() {
+        let result = BookingManager::try_create_multi(100, Some(7), vec![car(1), car(2)]);
+
+        assert!(matches!(result, Err(BookingError::BookingConflict { booking_id, .. }) if booking_id == "1"));
+        assert!(matches!(
+            BookingManager::try_create_multi::<TripItem, u32, i64, ()>(100, None, vec![]),
+            Err(BookingError::CreationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_multi_booking_is_stored_atomically() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+        manager.try_insert(car(50)).unwrap();
+
+        let result = manager.try_create_multi_booking(100, Some(7), vec![flight(1, 9), hotel(2, "hotel-1", true), car(3)]);
+
+        assert!(matches!(result, Err(BookingError::BookingConflict { booking_id, .. }) if booking_id == "50"));
+        assert_eq!(manager.repository().len(), 1);
+    }
+
+    #[test]
+    fn test_multi_booking_lines_are_stored() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+
+        let multi = manager
+            .try_create_multi_booking(100, Some(7), vec![flight(1, 3), hotel(2, "hotel-1", true), car(3)])
+            .unwrap();
+
+        assert_eq!(multi.item_ids(), vec!["GA123", "hotel-1", "car-1"]);
+        assert_eq!(manager.repository().list_by_user(&7).unwrap().len(), 3);
+        assert!(multi.line(&2).unwrap().is_pending());
+
+        // The stored flight line now takes 2 of the 3 seats.
+        let result = manager.try_create_multi_booking(101, Some(8), vec![flight(4, 3)]);
+        assert!(matches!(result, Err(BookingError::QuantityExceeded)));
+    }
+
+    #[test]
+    fn test_multi_booking_serde_round_trip_with_system_time() {
+        let line = BookingManager::create(1, None, TripItem::Car { id: "car-1".into() }, SystemTime::UNIX_EPOCH, None, None::<()>);
+        let multi = BookingManager::try_create_multi(100, None, vec![line]).unwrap();
+
+        let json = serde_json::to_string(&multi).unwrap();
+        let restored: MultiBooking<TripItem, u32, SystemTime, ()> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, multi);
+    }
+
+    #[test]
+    fn test_aggregate_status_follows_lines() {
+        let mut multi = BookingManager::try_create_multi(100, Some(7), vec![flight(1, 3), hotel(2, "hotel-1", true), car(3)]).unwrap();
+        assert_eq!(multi.status(), MultiBookingStatus::All(BookingStatus::Pending));
+
+        BookingManager::confirm_line(&mut multi, &1).unwrap();
+        assert_eq!(multi.status(), MultiBookingStatus::Mixed);
+
+        BookingManager::confirm_line(&mut multi, &2).unwrap();
+        BookingManager::confirm_line(&mut multi, &3).unwrap();
+        BookingManager::cancel_line(&mut multi, &3).unwrap();
+        assert_eq!(multi.status(), MultiBookingStatus::PartiallyCanceled);
+        assert!(multi.is_active());
+        assert!(multi.line(&1).unwrap().is_confirmed());
+        assert_eq!(multi.canceled_lines().len(), 1);
+
+        BookingManager::cancel_line(&mut multi, &1).unwrap();
+        BookingManager::cancel_line(&mut multi, &2).unwrap();
+        assert_eq!(multi.status(), MultiBookingStatus::All(BookingStatus::Canceled));
+        assert!(multi.is_final());
+    }
+
+    #[test]
+    fn test_line_transitions_are_validated() {
+        let mut multi = BookingManager::try_create_multi(100, Some(7), vec![car(1)]).unwrap();
+        BookingManager::cancel_line(&mut multi, &1).unwrap();
+
+        assert!(matches!(
+            BookingManager::confirm_line(&mut multi, &1),
+            Err(BookingError::InvalidStatusTransition { .. })
+        ));
+        assert!(matches!(
+            BookingManager::cancel_line(&mut multi, &9),
+            Err(BookingError::BookingNotFound(id)) if id == "9"
+        ));
+    }
+
+    #[test]
+    fn test_cancel_booking_line_releases_only_that_item() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+        let mut multi = manager
+            .try_create_multi_booking(100, Some(7), vec![hotel(1, "hotel-1", true), car(2)])
+            .unwrap();
+        manager.confirm_booking_line(&mut multi, &1).unwrap();
+        manager.confirm_booking_line(&mut multi, &2).unwrap();
+
+        manager.cancel_booking_line(&mut multi, &2).unwrap();
+
+        assert_eq!(multi.status(), MultiBookingStatus::PartiallyCanceled);
+        assert!(manager.repository().get(&2).unwrap().unwrap().is_canceled());
+        assert!(manager.repository().get(&1).unwrap().unwrap().is_confirmed());
+        assert!(manager.try_insert(car(3)).is_ok());
+        assert!(matches!(manager.try_insert(hotel(4, "hotel-1", true)), Err(BookingError::BookingConflict { .. })));
+    }
+}
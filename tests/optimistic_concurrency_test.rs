@@ -0,0 +1,168 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::traits::Bookable;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Room {
+        pub id: &'static str,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    type Repo = InMemoryBookingRepository<Room, u32, i64, ()>;
+    type Change = fn(&mut Booking<Room, u32, i64, ()>) -> Result<(), BookingError>;
+
+    fn room(booking_id: u32) -> Booking<Room, u32, i64, ()> {
+        BookingManager::create(booking_id, None, Room { id: "room-1" }, 0, None, None)
+    }
+
+    #[test]
+    fn test_every_stored_mutation_increments_version() {
+        let manager = BookingManager::new(Repo::new());
+
+        assert_eq!(manager.create_booking(1, None, Room { id: "room-1" }, 0, None, None).unwrap().version, 0);
+        assert_eq!(manager.confirm_booking(&1).unwrap().version, 1);
+        assert_eq!(manager.complete_booking(&1).unwrap().version, 2);
+        assert_eq!(manager.repository().get(&1).unwrap().unwrap().version, 2);
+
+        // Rejected transitions leave the stored booking untouched.
+        assert!(manager.cancel_booking(&1).is_err());
+        assert_eq!(manager.repository().get(&1).unwrap().unwrap().version, 2);
+    }
+
+    #[test]
+    fn test_stale_update_is_rejected() {
+        let repository = Repo::new();
+        repository.insert(room(1)).unwrap();
+
+        let mut first = repository.get(&1).unwrap().unwrap();
+        let mut second = first.clone();
+        BookingManager::confirm(&mut first).unwrap();
+        repository.update(first).unwrap();
+
+        BookingManager::try_cancel(&mut second).unwrap();
+        let result = repository.update(second);
+
+        assert!(matches!(
+            result,
+            Err(BookingError::ConcurrentModification { ref booking_id, version: 1, stored: 1 }) if booking_id == "1"
+        ));
+        assert_eq!(result.unwrap_err().code(), "concurrent_modification");
+        assert!(repository.get(&1).unwrap().unwrap().is_confirmed());
+    }
+
+    #[test]
+    fn test_concurrent_confirm_and_cancel_cannot_both_succeed() {
+        let changes: [Change; 2] = [BookingManager::confirm, BookingManager::try_cancel];
+
+        for _ in 0..50 {
+            let repository = Arc::new(Repo::new());
+            repository.insert(room(1)).unwrap();
+            let loaded = repository.get(&1).unwrap().unwrap();
+            let barrier = Arc::new(Barrier::new(2));
+
+            // Both writers change the same loaded version, then race to store it.
+            let handles: Vec<_> = changes
+                .into_iter()
+                .map(|change| {
+                    let (repository, barrier, mut booking) = (Arc::clone(&repository), Arc::clone(&barrier), loaded.clone());
+                    thread::spawn(move || {
+                        change(&mut booking).unwrap();
+                        barrier.wait();
+                        repository.update(booking)
+                    })
+                })
+                .collect();
+            let results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+            assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+            assert_eq!(
+                results
+                    .iter()
+                    .filter(|result| matches!(result, Err(BookingError::ConcurrentModification { version: 1, stored: 1, .. })))
+                    .count(),
+                1
+            );
+            let stored = repository.get(&1).unwrap().unwrap();
+            assert_eq!(stored.version, 1);
+            assert_eq!(stored.history.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_retry_on_conflict_retries_only_conflicts() {
+        let mut calls = 0;
+        let result = BookingManager::retry_on_conflict(3, || {
+            calls += 1;
+            match calls {
+                1 | 2 => Err(BookingError::new_concurrent_modification("1", 1, 1)),
+                _ => Ok(calls),
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+
+        let mut calls = 0;
+        let result: Result<(), _> = BookingManager::retry_on_conflict(2, || {
+            calls += 1;
+            Err(BookingError::new_concurrent_modification("1", 1, 1))
+        });
+        assert!(matches!(result, Err(BookingError::ConcurrentModification { .. })));
+        assert_eq!(calls, 2);
+
+        let mut calls = 0;
+        let result: Result<(), _> = BookingManager::retry_on_conflict(5, || {
+            calls += 1;
+            Err(BookingError::new_booking_not_found("1"))
+        });
+        assert!(matches!(result, Err(BookingError::BookingNotFound(_))));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_retry_reapplies_change_on_latest_version() {
+        let manager = BookingManager::new(Repo::new());
+        manager.try_insert(room(1)).unwrap();
+
+        // Another node confirms the booking between our read and our write on the first attempt.
+        let mut attempts = 0;
+        let canceled = BookingManager::retry_on_conflict(3, || {
+            attempts += 1;
+            let mut booking = manager.repository().get(&1)?.unwrap();
+            if attempts == 1 {
+                manager.confirm_booking(&1)?;
+            }
+            BookingManager::try_cancel(&mut booking)?;
+            manager.repository().update(booking.clone())?;
+            Ok(booking)
+        })
+        .unwrap();
+
+        assert_eq!(attempts, 2);
+        assert_eq!(canceled.version, 2);
+        assert_eq!(canceled.history.len(), 2);
+    }
+
+    #[test]
+    fn test_version_defaults_to_zero_when_deserialized() {
+        let json = r#"{"id":1,"item":"room-1","status":"Pending","created_at":0}"#;
+
+        let booking: Booking<String, u32, i64, ()> = serde_json::from_str(json).unwrap();
+
+        assert_eq!(booking.version, 0);
+    }
+}
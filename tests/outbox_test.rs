@@ -0,0 +1,292 @@
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, OpenOptions};
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use booking_kit::error::BookingError;
+    use booking_kit::events::BookingEvent;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::history::ChangeContext;
+    use booking_kit::model::status::{BookingStatus, Status};
+    use booking_kit::outbox::{BookingOutbox, DeduplicatingPublisher, EventPublisher, OutboxMessage, OutboxRelay};
+    use booking_kit::policy::TransitionPolicy;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::file::TruncatedTail;
+    use booking_kit::repository::file_outbox::FileOutboxRepository;
+    use booking_kit::traits::Bookable;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Room {
+        pub id: String,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    type Repository = FileOutboxRepository<Room, u32, i64, ()>;
+    type VisitRepository = FileOutboxRepository<Room, u32, i64, (), VisitStatus>;
+    type Clinic = BookingManager<VisitRepository, (), (), (), (), VisitStatus>;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum VisitStatus {
+        Booked,
+        CheckedIn,
+        Canceled,
+    }
+
+    impl Status for VisitStatus {
+        const ALL: &'static [Self] = &[VisitStatus::Booked, VisitStatus::CheckedIn, VisitStatus::Canceled];
+
+        fn is_active(&self) -> bool {
+            !self.is_final()
+        }
+
+        fn is_final(&self) -> bool {
+            matches!(self, VisitStatus::Canceled)
+        }
+
+        fn can_transition_to(&self, next: &Self) -> bool {
+            matches!(
+                (self, next),
+                (VisitStatus::Booked, VisitStatus::CheckedIn | VisitStatus::Canceled) | (VisitStatus::CheckedIn, VisitStatus::Canceled)
+            )
+        }
+
+        fn from_booking_status(status: &BookingStatus) -> Option<Self> {
+            match status {
+                BookingStatus::Pending => Some(VisitStatus::Booked),
+                BookingStatus::Canceled => Some(VisitStatus::Canceled),
+                _ => None,
+            }
+        }
+    }
+
+    /// Records published message IDs, failing while `failures` is above zero.
+    #[derive(Default)]
+    struct RecordingPublisher {
+        published: Mutex<Vec<u64>>,
+        failures: Mutex<u32>,
+    }
+
+    impl EventPublisher<u32, i64> for RecordingPublisher {
+        fn publish(&self, message: &OutboxMessage<u32, i64>) -> Result<(), BookingError> {
+            let mut failures = self.failures.lock().unwrap();
+            if *failures > 0 {
+                *failures -= 1;
+                return Err(BookingError::new_storage_error("broker unavailable"));
+            }
+            self.published.lock().unwrap().push(message.id);
+            Ok(())
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("booking_kit_outbox_{}_{}.jsonl", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn room(booking_id: u32, room_id: &str) -> Booking<Room, u32, i64, ()> {
+        BookingManager::create(booking_id, None, Room { id: room_id.into() }, 0, None, None)
+    }
+
+    #[test]
+    fn test_writes_store_their_events() {
+        let path = temp_path("writes");
+        let manager = BookingManager::new(Repository::open(&path).unwrap());
+
+        manager.try_insert(room(1, "room-1")).unwrap();
+        manager.confirm_booking(&1).unwrap();
+
+        let messages = manager.repository().pending_messages(10).unwrap();
+        assert_eq!(messages.iter().map(|m| m.id).collect::<Vec<_>>(), vec![1, 2]);
+        assert!(matches!(&messages[0].event, BookingEvent::Created(data) if data.booking_id == 1));
+        assert!(matches!(&messages[1].event, BookingEvent::Confirmed(data) if data.from == Some(BookingStatus::Pending)));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_reopening_restores_bookings_and_undelivered_events() {
+        let path = temp_path("reopen");
+        {
+            let manager = BookingManager::new(Repository::open(&path).unwrap());
+            manager.try_insert(room(1, "room-1")).unwrap();
+            manager.cancel_booking(&1).unwrap();
+            manager.repository().acknowledge(1).unwrap();
+        }
+
+        let repository = Repository::open(&path).unwrap();
+
+        assert!(repository.get(&1).unwrap().unwrap().is_canceled());
+        let messages = repository.pending_messages(10).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert!(matches!(messages[0].event, BookingEvent::Canceled(_)));
+
+        repository.insert(room(2, "room-2")).unwrap();
+        assert_eq!(repository.pending_messages(10).unwrap()[1].id, 3);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_relay_drains_outbox_in_order() {
+        let path = temp_path("relay");
+        let repository = Repository::open(&path).unwrap();
+        for booking_id in 1..=5 {
+            repository.insert(room(booking_id, "room-1")).unwrap();
+        }
+        let relay = OutboxRelay::new(RecordingPublisher::default()).with_batch_size(2);
+
+        let report = relay.relay(&repository).unwrap();
+
+        assert!(report.is_complete());
+        assert_eq!(report.delivered, vec![1, 2, 3, 4, 5]);
+        assert!(repository.pending_messages(10).unwrap().is_empty());
+        assert!(relay.relay(&repository).unwrap().delivered.is_empty());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_failed_delivery_is_retried() {
+        let path = temp_path("retry");
+        let repository = Repository::open(&path).unwrap();
+        repository.insert(room(1, "room-1")).unwrap();
+        repository.insert(room(2, "room-2")).unwrap();
+        let publisher = RecordingPublisher::default();
+        *publisher.failures.lock().unwrap() = 1;
+        let relay = OutboxRelay::new(publisher);
+
+        let report = relay.relay(&repository).unwrap();
+        assert!(report.delivered.is_empty());
+        assert!(matches!(report.failed, Some((1, BookingError::StorageError(_)))));
+        assert_eq!(repository.pending_messages(10).unwrap().len(), 2);
+
+        let report = relay.relay(&repository).unwrap();
+        assert_eq!(report.delivered, vec![1, 2]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_deduplicating_publisher_skips_redelivered_messages() {
+        let publisher = DeduplicatingPublisher::new(RecordingPublisher::default());
+        let message = OutboxMessage {
+            id: 7,
+            event: BookingEvent::created(&room(1, "room-1")),
+        };
+
+        publisher.publish(&message).unwrap();
+        publisher.publish(&message).unwrap();
+
+        assert_eq!(*publisher.inner().published.lock().unwrap(), vec![7]);
+    }
+
+    #[test]
+    fn test_rolled_back_lines_leave_no_events() {
+        let path = temp_path("rollback");
+        let manager = BookingManager::new(Repository::open(&path).unwrap());
+        manager.try_insert(room(2, "room-9")).unwrap();
+
+        let result = manager.try_create_multi_booking(100, None, vec![room(1, "room-1"), room(2, "room-2")]);
+
+        assert!(matches!(result, Err(BookingError::DuplicateBooking(_))));
+        assert!(manager.repository().get(&1).unwrap().is_none());
+        let messages = manager.repository().pending_messages(10).unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].event.booking_id(), &2);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_truncated_trailing_line_is_dropped_on_open() {
+        let path = temp_path("truncated");
+        {
+            let repository = Repository::open(&path).unwrap();
+            repository.insert(room(1, "room-1")).unwrap();
+        }
+        let complete = fs::metadata(&path).unwrap().len();
+        let partial = r#"{"op":"insert","booking":{"id":2,"item":{"id":"ro"#;
+        OpenOptions::new().append(true).open(&path).unwrap().write_all(partial.as_bytes()).unwrap();
+
+        let repository = Repository::open(&path).unwrap();
+
+        assert_eq!(
+            repository.truncated_tail(),
+            Some(&TruncatedTail { line: 2, offset: complete, len: partial.len() })
+        );
+        assert!(repository.get(&2).unwrap().is_none());
+        repository.insert(room(2, "room-2")).unwrap();
+        drop(repository);
+
+        let reopened = Repository::open(&path).unwrap();
+        assert!(reopened.truncated_tail().is_none());
+        assert_eq!(reopened.pending_messages(10).unwrap().iter().map(|m| m.id).collect::<Vec<_>>(), vec![1, 2]);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_compaction_keeps_undelivered_events_and_message_ids() {
+        let path = temp_path("compact");
+        let manager = BookingManager::new(Repository::open(&path).unwrap());
+        manager.try_insert(room(1, "room-1")).unwrap();
+        manager.try_insert(room(2, "room-2")).unwrap();
+        manager.confirm_booking(&1).unwrap();
+        manager.repository().acknowledge(1).unwrap();
+        manager.repository().acknowledge(2).unwrap();
+        manager.repository().acknowledge(3).unwrap();
+        manager.cancel_booking(&2).unwrap();
+        let before = fs::read(&path).unwrap();
+
+        manager.repository().compact().unwrap();
+
+        assert_eq!(manager.repository().log_len().unwrap(), 0);
+        let snapshot_path = manager.repository().snapshot_path().to_path_buf();
+        drop(manager);
+
+        // Simulates a crash after the snapshot was renamed but before the log was emptied.
+        fs::write(&path, before).unwrap();
+        let repository = Repository::open(&path).unwrap();
+
+        assert!(repository.get(&1).unwrap().unwrap().is_confirmed());
+        assert!(repository.get(&2).unwrap().unwrap().is_canceled());
+        let messages = repository.pending_messages(10).unwrap();
+        assert_eq!(messages.iter().map(|m| m.id).collect::<Vec<_>>(), vec![4]);
+        assert!(matches!(messages[0].event, BookingEvent::Canceled(_)));
+
+        // Message IDs are not reused once the acknowledged messages are compacted away.
+        repository.compact().unwrap();
+        repository.acknowledge(4).unwrap();
+        repository.compact().unwrap();
+        drop(repository);
+        let repository = Repository::open(&path).unwrap();
+        repository.insert(room(3, "room-3")).unwrap();
+        assert_eq!(repository.pending_messages(10).unwrap()[0].id, 5);
+        fs::remove_file(path).unwrap();
+        fs::remove_file(snapshot_path).unwrap();
+    }
+
+    #[test]
+    fn test_custom_statuses_are_stored_with_their_events() {
+        let path = temp_path("custom_status");
+        let manager: Clinic = BookingManager::new(VisitRepository::open(&path).unwrap()).with_policy(TransitionPolicy::default());
+        manager.create_booking(1, None, Room { id: "room-1".into() }, 0, None, None).unwrap();
+        manager.transition_booking(&1, VisitStatus::CheckedIn, ChangeContext::new().at(10)).unwrap();
+        drop(manager);
+
+        let repository = VisitRepository::open(&path).unwrap();
+
+        assert_eq!(repository.get(&1).unwrap().unwrap().status, VisitStatus::CheckedIn);
+        let messages = repository.pending_messages(10).unwrap();
+        assert!(matches!(&messages[0].event, BookingEvent::Created(data) if data.to == VisitStatus::Booked));
+        assert!(matches!(&messages[1].event, BookingEvent::Modified(data) if data.to == VisitStatus::CheckedIn));
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::status::BookingStatus;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::traits::Bookable;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct MockBookable {
+        pub id: &'static str,
+        pub available: bool,
+    }
+
+    impl Bookable for MockBookable {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            self.available
+        }
+    }
+
+    type Repo = InMemoryBookingRepository<MockBookable, u32, i64, ()>;
+
+    #[test]
+    fn test_create_booking_persists() {
+        let manager = BookingManager::new(Repo::new());
+        let item = MockBookable { id: "room-1", available: true };
+
+        let booking = manager.create_booking(1, Some(10), item, 0, None, None).unwrap();
+        let stored = manager.repository().get(&1).unwrap().unwrap();
+
+        assert_eq!(stored, booking);
+        assert_eq!(stored.status, BookingStatus::Pending);
+    }
+
+    #[test]
+    fn test_create_booking_rejects_duplicate_id() {
+        let manager = BookingManager::new(Repo::new());
+        let item = MockBookable { id: "room-1", available: true };
+
+        manager.create_booking(1, None, item.clone(), 0, None, None).unwrap();
+        let result = manager.create_booking(1, None, item, 0, None, None);
+
+        assert!(matches!(result, Err(BookingError::DuplicateBooking(id)) if id == "1"));
+        assert_eq!(manager.repository().len(), 1);
+    }
+
+    #[test]
+    fn test_try_create_booking_does_not_store_unavailable_item() {
+        let manager = BookingManager::new(Repo::new());
+        let item = MockBookable { id: "room-2", available: false };
+
+        let result = manager.try_create_booking(1, None, item, 0, None, None);
+
+        assert!(matches!(result, Err(BookingError::ItemUnavailable(id)) if id == "room-2"));
+        assert!(manager.repository().is_empty());
+    }
+
+    #[test]
+    fn test_confirm_and_cancel_booking_are_persisted() {
+        let manager = BookingManager::new(Repo::new());
+        let item = MockBookable { id: "room-3", available: true };
+        manager.create_booking(1, None, item.clone(), 0, None, None).unwrap();
+        manager.create_booking(2, None, item, 0, None, None).unwrap();
+
+        manager.confirm_booking(&1).unwrap();
+        manager.cancel_booking(&2).unwrap();
+
+        let repo = manager.repository();
+        assert_eq!(repo.get(&1).unwrap().unwrap().status, BookingStatus::Confirmed);
+        assert_eq!(repo.get(&2).unwrap().unwrap().status, BookingStatus::Canceled);
+    }
+
+    #[test]
+    fn test_transition_on_missing_booking_fails() {
+        let manager = BookingManager::new(Repo::new());
+        let result = manager.expire_booking(&404);
+
+        assert!(matches!(result, Err(BookingError::BookingNotFound(id)) if id == "404"));
+    }
+
+    #[test]
+    fn test_list_by_user_and_item() {
+        let manager = BookingManager::new(Repo::new());
+        let room = MockBookable { id: "room-4", available: true };
+        let car = MockBookable { id: "car-1", available: true };
+        manager.create_booking(1, Some(7), room.clone(), 0, None, None).unwrap();
+        manager.create_booking(2, Some(8), room, 0, None, None).unwrap();
+        manager.create_booking(3, Some(7), car, 0, None, None).unwrap();
+
+        let by_user: Vec<u32> = manager.repository().list_by_user(&7).unwrap().iter().map(|b| b.id).collect();
+        let by_item: Vec<u32> = manager.repository().list_by_item("room-4").unwrap().iter().map(|b| b.id).collect();
+
+        assert_eq!(by_user, vec![1, 3]);
+        assert_eq!(by_item, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_repository_is_shareable_between_threads() {
+        let manager = Arc::new(BookingManager::new(Repo::new()));
+
+        let handles: Vec<_> = (0..8)
+            .map(|id| {
+                let manager = Arc::clone(&manager);
+                thread::spawn(move || {
+                    let item = MockBookable { id: "room-5", available: true };
+                    manager.create_booking(id, None, item, 0, None, None).unwrap();
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(manager.repository().len(), 8);
+    }
+}
@@ -0,0 +1,206 @@
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+    use std::thread;
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::history::ChangeContext;
+    use booking_kit::model::status::BookingStatus;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::sqlite::SqliteBookingRepository;
+    use booking_kit::traits::Bookable;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Room {
+        pub id: String,
+        pub floor: u8,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Payment {
+        pub reference: String,
+    }
+
+    type Repo = SqliteBookingRepository<Room, String, i64, Payment>;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("booking_kit_sqlite_{}_{}.db", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn night(booking_id: &str, user_id: &str, room_id: &str) -> Booking<Room, String, i64, Payment> {
+        let room = Room { id: room_id.into(), floor: 3 };
+        let payment = Payment { reference: format!("pay-{booking_id}") };
+        BookingManager::create(booking_id.into(), Some(user_id.into()), room, 0, Some(600), Some(payment)).with_period(100, 200)
+    }
+
+    #[test]
+    fn test_bookings_round_trip_through_manager() {
+        let manager = BookingManager::new(Repo::open_in_memory().unwrap());
+
+        let stored = manager.try_insert(night("b-1", "u-1", "room-1")).unwrap();
+        let confirmed = manager
+            .transition_booking(&"b-1".to_string(), BookingStatus::Confirmed, ChangeContext::new().at(10).by("u-1".into()))
+            .unwrap();
+
+        let loaded = manager.repository().get(&"b-1".to_string()).unwrap().unwrap();
+        assert_eq!(loaded, confirmed);
+        assert_eq!(loaded.version, 1);
+        assert_eq!(loaded.period, stored.period);
+        assert_eq!(loaded.metadata, stored.metadata);
+        assert!(manager.repository().get(&"b-9".to_string()).unwrap().is_none());
+
+        // Overlapping bookings are still detected against the stored rows.
+        assert!(matches!(manager.try_insert(night("b-2", "u-2", "room-1")), Err(BookingError::BookingConflict { .. })));
+    }
+
+    #[test]
+    fn test_duplicate_and_missing_bookings_are_rejected() {
+        let repository = Repo::open_in_memory().unwrap();
+        repository.insert(night("b-1", "u-1", "room-1")).unwrap();
+
+        let duplicate = repository.insert(night("b-1", "u-2", "room-2"));
+        assert!(matches!(duplicate, Err(BookingError::DuplicateBooking(id)) if id == "b-1"));
+
+        let mut missing = night("b-2", "u-1", "room-1");
+        missing.version = 1;
+        assert!(matches!(repository.update(missing), Err(BookingError::BookingNotFound(_))));
+        assert!(repository.remove(&"b-2".to_string()).unwrap().is_none());
+        assert_eq!(repository.remove(&"b-1".to_string()).unwrap().unwrap().id, "b-1");
+        assert!(repository.get(&"b-1".to_string()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_listings_are_in_insertion_order() {
+        let repository = Repo::open_in_memory().unwrap();
+        repository.insert(night("b-1", "u-1", "room-1")).unwrap();
+        repository.insert(night("b-2", "u-2", "room-1")).unwrap();
+        repository.insert(night("b-3", "u-1", "room-2")).unwrap();
+        repository.transition(&"b-2".to_string(), BookingStatus::Canceled, ChangeContext::new()).unwrap();
+
+        let ids = |bookings: Vec<Booking<Room, String, i64, Payment>>| bookings.into_iter().map(|b| b.id).collect::<Vec<_>>();
+        assert_eq!(ids(repository.list_by_user(&"u-1".to_string()).unwrap()), vec!["b-1", "b-3"]);
+        assert_eq!(ids(repository.list_by_item("room-1").unwrap()), vec!["b-1", "b-2"]);
+        assert_eq!(ids(repository.list_by_status(&BookingStatus::Pending).unwrap()), vec!["b-1", "b-3"]);
+        assert_eq!(ids(repository.list_by_status(&BookingStatus::Canceled).unwrap()), vec!["b-2"]);
+    }
+
+    #[test]
+    fn test_insert_checked_sees_related_rows_and_stores_all_or_nothing() {
+        let repository = Repo::open_in_memory().unwrap();
+        repository.insert(night("b-1", "u-1", "room-1")).unwrap();
+        repository.insert(night("b-2", "u-2", "room-2")).unwrap();
+        repository.insert(night("b-3", "u-3", "room-3")).unwrap();
+
+        let mut seen = Vec::new();
+        let rejected = repository.insert_checked(vec![night("b-4", "u-2", "room-1"), night("b-5", "u-9", "room-9")], &mut |stored| {
+            seen = stored.iter().map(|b| b.id.clone()).collect();
+            Err(BookingError::new_creation_failed("rejected"))
+        });
+        assert!(matches!(rejected, Err(BookingError::CreationFailed(_))));
+        assert_eq!(seen, vec!["b-1", "b-2"]);
+        assert!(repository.get(&"b-4".to_string()).unwrap().is_none());
+
+        let duplicate = repository.insert_checked(vec![night("b-6", "u-1", "room-1"), night("b-6", "u-1", "room-2")], &mut |_| Ok(()));
+        assert!(matches!(duplicate, Err(BookingError::DuplicateBooking(id)) if id == "b-6"));
+
+        repository.insert_checked(vec![night("b-4", "u-2", "room-1"), night("b-5", "u-9", "room-9")], &mut |_| Ok(())).unwrap();
+        assert_eq!(repository.list_by_item("room-9").unwrap().len(), 1);
+        assert_eq!(repository.list_by_user(&"u-2".to_string()).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_update_checked_sees_other_related_rows_and_stores_nothing_on_rejection() {
+        let repository = Repo::open_in_memory().unwrap();
+        repository.insert(night("b-1", "u-1", "room-1")).unwrap();
+        repository.insert(night("b-2", "u-2", "room-1")).unwrap();
+        repository.insert(night("b-3", "u-1", "room-3")).unwrap();
+        repository.insert(night("b-4", "u-4", "room-4")).unwrap();
+        let mut confirmed = repository.get(&"b-1".to_string()).unwrap().unwrap();
+        BookingManager::confirm(&mut confirmed).unwrap();
+
+        let mut seen = Vec::new();
+        let rejected = repository.update_checked(confirmed.clone(), &mut |stored| {
+            seen = stored.iter().map(|b| b.id.clone()).collect();
+            Err(BookingError::QuantityExceeded)
+        });
+        assert!(matches!(rejected, Err(BookingError::QuantityExceeded)));
+        assert_eq!(seen, vec!["b-2", "b-3"]);
+        assert!(repository.get(&"b-1".to_string()).unwrap().unwrap().is_pending());
+
+        repository.update_checked(confirmed, &mut |_| Ok(())).unwrap();
+        assert!(repository.get(&"b-1".to_string()).unwrap().unwrap().is_confirmed());
+    }
+
+    #[test]
+    fn test_transition_rechecks_status_in_transaction() {
+        let repository = Repo::open_in_memory().unwrap();
+        repository.insert(night("b-1", "u-1", "room-1")).unwrap();
+        let id = "b-1".to_string();
+
+        let canceled = repository.transition(&id, BookingStatus::Canceled, ChangeContext::new().at(5).because("guest request")).unwrap();
+        assert_eq!(canceled.version, 1);
+        assert_eq!(canceled.last_change().unwrap().reason.as_deref(), Some("guest request"));
+
+        let result = repository.transition(&id, BookingStatus::Confirmed, ChangeContext::new());
+        assert!(matches!(result, Err(BookingError::InvalidStatusTransition { from: BookingStatus::Canceled, .. })));
+        assert_eq!(repository.get(&id).unwrap().unwrap(), canceled);
+    }
+
+    #[test]
+    fn test_stale_update_is_rejected() {
+        let repository = Repo::open_in_memory().unwrap();
+        repository.insert(night("b-1", "u-1", "room-1")).unwrap();
+        let mut stale = repository.get(&"b-1".to_string()).unwrap().unwrap();
+        repository.transition(&"b-1".to_string(), BookingStatus::Confirmed, ChangeContext::new()).unwrap();
+
+        BookingManager::try_cancel(&mut stale).unwrap();
+
+        assert!(matches!(
+            repository.update(stale),
+            Err(BookingError::ConcurrentModification { version: 1, stored: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_database_file_is_shared_between_connections() {
+        let path = temp_path("shared");
+        let id = "b-1".to_string();
+        {
+            let repository = Repo::open(&path).unwrap();
+            repository.insert(night("b-1", "u-1", "room-1")).unwrap();
+            repository.transition(&id, BookingStatus::Confirmed, ChangeContext::new()).unwrap();
+        }
+
+        // Completing and canceling a confirmed booking exclude each other, so exactly one of two
+        // processes racing on the same file succeeds.
+        let handles: Vec<_> = [BookingStatus::Completed, BookingStatus::Canceled]
+            .into_iter()
+            .map(|to| {
+                let (path, id) = (path.clone(), id.clone());
+                thread::spawn(move || Repo::open(&path).unwrap().transition(&id, to, ChangeContext::new()).is_ok())
+            })
+            .collect();
+        let succeeded = handles.into_iter().map(|h| h.join().unwrap()).filter(|ok| *ok).count();
+
+        let stored = Repo::open(&path).unwrap().get(&id).unwrap().unwrap();
+        assert_eq!(succeeded, 1);
+        assert_eq!(stored.version, 2);
+        assert!(stored.is_final());
+        fs::remove_file(path).unwrap();
+    }
+}
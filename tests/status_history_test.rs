@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::history::ChangeContext;
+    use booking_kit::model::status::BookingStatus;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::traits::Bookable;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Room {
+        pub id: String,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    fn room() -> Room {
+        Room { id: "room-1".into() }
+    }
+
+    #[test]
+    fn test_every_transition_is_recorded() {
+        let mut booking = BookingManager::create(1, None, room(), 0, None, None::<()>);
+        BookingManager::confirm(&mut booking).unwrap();
+        BookingManager::complete(&mut booking).unwrap();
+
+        let steps: Vec<(BookingStatus, BookingStatus)> =
+            booking.history.iter().map(|c| (c.from.clone(), c.to.clone())).collect();
+
+        assert_eq!(
+            steps,
+            vec![
+                (BookingStatus::Pending, BookingStatus::Confirmed),
+                (BookingStatus::Confirmed, BookingStatus::Completed),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejected_transition_is_not_recorded() {
+        let mut booking = BookingManager::create(1, None, room(), 0, None, None::<()>);
+        BookingManager::try_expire(&mut booking).unwrap();
+
+        assert!(BookingManager::confirm(&mut booking).is_err());
+        assert_eq!(booking.history.len(), 1);
+    }
+
+    #[test]
+    fn test_actor_reason_and_timestamp_are_recorded_through_repository() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+        manager.create_booking(1, Some(7), room(), 100, None, None::<()>).unwrap();
+
+        let context = ChangeContext::new().at(250).by(42).because("customer request");
+        manager.transition_booking(&1, BookingStatus::Canceled, context).unwrap();
+
+        let stored = manager.repository().get(&1).unwrap().unwrap();
+        let change = stored.last_change().unwrap();
+        assert_eq!(change.from, BookingStatus::Pending);
+        assert_eq!(change.to, BookingStatus::Canceled);
+        assert_eq!(change.at, Some(250));
+        assert_eq!(change.actor, Some(42));
+        assert_eq!(change.reason.as_deref(), Some("customer request"));
+    }
+
+    #[test]
+    fn test_history_is_serialized_with_booking() {
+        let mut booking: Booking<Room, String, i64, ()> =
+            BookingManager::create("booking-1".into(), None, room(), 0, None, None);
+        let fresh = serde_json::to_value(&booking).unwrap();
+        assert!(fresh.get("history").is_none());
+
+        let context = ChangeContext::new().at(10).by("agent-1".to_string()).because("paid");
+        BookingManager::transition_with(&Default::default(), &mut booking, BookingStatus::Confirmed, context).unwrap();
+
+        let json = serde_json::to_string(&booking).unwrap();
+        let restored: Booking<Room, String, i64, ()> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, booking);
+        assert_eq!(restored.history[0].actor.as_deref(), Some("agent-1"));
+    }
+}
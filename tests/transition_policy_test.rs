@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::history::ChangeContext;
+    use booking_kit::model::status::BookingStatus;
+    use booking_kit::policy::TransitionPolicy;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::traits::Bookable;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct MockBookable {
+        pub id: &'static str,
+    }
+
+    impl Bookable for MockBookable {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    const ALL: [BookingStatus; 6] = [
+        BookingStatus::Pending,
+        BookingStatus::Confirmed,
+        BookingStatus::Canceled,
+        BookingStatus::Expired,
+        BookingStatus::Failed,
+        BookingStatus::Completed,
+    ];
+
+    #[test]
+    fn test_default_policy_matches_can_transition_to() {
+        let policy = TransitionPolicy::default();
+
+        for from in &ALL {
+            for to in &ALL {
+                assert_eq!(policy.allows(from, to), from.can_transition_to(to), "{:?} -> {:?}", from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn test_policy_json_round_trip() {
+        let policy = TransitionPolicy::default().allow(BookingStatus::Canceled, BookingStatus::Pending);
+        let json = policy.to_json().unwrap();
+
+        assert_eq!(TransitionPolicy::from_json(&json).unwrap(), policy);
+    }
+
+    #[test]
+    fn test_invalid_policy_json_is_rejected() {
+        let result = TransitionPolicy::from_json(r#"{"transitions": [{"from": "Pending", "to": "Nowhere"}]}"#);
+
+        assert!(matches!(result, Err(BookingError::GeneralError(_))));
+    }
+
+    #[test]
+    fn test_manager_uses_custom_policy() {
+        let policy = TransitionPolicy::default()
+            .allow(BookingStatus::Canceled, BookingStatus::Pending)
+            .forbid(BookingStatus::Pending, BookingStatus::Canceled);
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_policy(policy);
+        manager.create_booking(1, None, MockBookable { id: "room-1" }, 0, None, None::<()>).unwrap();
+
+        let result = manager.cancel_booking(&1);
+        assert!(matches!(
+            result,
+            Err(BookingError::InvalidStatusTransition { from: BookingStatus::Pending, to: BookingStatus::Canceled })
+        ));
+
+        manager.confirm_booking(&1).unwrap();
+        manager.cancel_booking(&1).unwrap();
+        let reinstated = manager.transition_booking(&1, BookingStatus::Pending, ChangeContext::new()).unwrap();
+        assert_eq!(reinstated.status, BookingStatus::Pending);
+    }
+
+    #[test]
+    fn test_manager_transition_leaves_booking_unchanged_on_error() {
+        let manager = BookingManager::new(()).with_policy(TransitionPolicy::empty());
+        let mut booking = BookingManager::create(1, None, MockBookable { id: "room-1" }, 0, None, None::<()>);
+
+        assert!(manager.transition(&mut booking, BookingStatus::Confirmed, ChangeContext::new()).is_err());
+        assert_eq!(booking.status, BookingStatus::Pending);
+    }
+}
@@ -0,0 +1,195 @@
+#[cfg(test)]
+mod tests {
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::history::ChangeContext;
+    use booking_kit::model::status::BookingStatus;
+    use std::marker::PhantomData;
+    use booking_kit::model::status::Status;
+    use booking_kit::model::typestate::{
+        AnyBooking, BookingState, Canceled, CheckedIn, Completed, Confirmed, Expired, Failed, NoShow, OnHold, Pending,
+        Refunded, TransitionTo, TypedBooking, Waitlisted,
+    };
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::traits::Bookable;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct MockBookable {
+        pub id: String,
+    }
+
+    impl Bookable for MockBookable {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    /// Tells at compile time whether `From: TransitionTo<To>`: method resolution picks
+    /// `Implemented` on `&Probe` when the impl exists and falls back to `NotImplemented` on
+    /// `&&Probe` otherwise.
+    struct Probe<From, To>(PhantomData<(From, To)>);
+
+    trait Implemented {
+        fn implemented(&self) -> bool {
+            true
+        }
+    }
+
+    impl<From: TransitionTo<To>, To: BookingState> Implemented for Probe<From, To> {}
+
+    trait NotImplemented {
+        fn implemented(&self) -> bool {
+            false
+        }
+    }
+
+    impl<From, To> NotImplemented for &Probe<From, To> {}
+
+    /// Lists `(from, to, has TransitionTo impl)` for every ordered pair of the given markers.
+    macro_rules! typed_transitions {
+        ($($state:ty),*) => {
+            typed_transitions!(@rows [$($state),*] [$($state),*])
+        };
+        (@rows [$($from:ty),*] $to:tt) => {
+            [$(typed_transitions!(@row $from $to)),*].concat()
+        };
+        (@row $from:ty [$($to:ty),*]) => {
+            [$((
+                <$from as BookingState>::STATUS,
+                <$to as BookingState>::STATUS,
+                (&Probe::<$from, $to>(PhantomData)).implemented(),
+            )),*]
+        };
+    }
+
+    fn pending(id: u32) -> TypedBooking<Pending, MockBookable, u32, i64, ()> {
+        TypedBooking::create(id, Some(7), MockBookable { id: "item_1".into() }, 0, None, None)
+    }
+
+    fn with_status(id: u32, status: BookingStatus) -> Booking<MockBookable, u32, i64, ()> {
+        let mut booking = BookingManager::create(id, None, MockBookable { id: "item_1".into() }, 0, None, None);
+        booking.status = status;
+        booking
+    }
+
+    #[test]
+    fn test_transitions_record_history() {
+        let booking = pending(1)
+            .confirm()
+            .transition_with::<Completed>(ChangeContext::new().at(100).by(7).because("stay finished"));
+
+        assert_eq!(booking.status(), BookingStatus::Completed);
+        assert_eq!(booking.history.len(), 2);
+        assert_eq!(booking.history[0].from, BookingStatus::Pending);
+        assert_eq!(booking.history[1].to, BookingStatus::Completed);
+        assert_eq!(booking.history[1].reason.as_deref(), Some("stay finished"));
+    }
+
+    #[test]
+    fn test_typed_transitions_match_dynamic_transitions() {
+        let typed = pending(1).cancel();
+
+        let mut dynamic = BookingManager::create(1, Some(7), MockBookable { id: "item_1".into() }, 0, None, None::<()>);
+        BookingManager::try_cancel(&mut dynamic).unwrap();
+
+        assert_eq!(Booking::from(typed), dynamic);
+    }
+
+    #[test]
+    fn test_try_from_checks_status() {
+        let confirmed = TypedBooking::<Confirmed, _, _, _, _>::try_from(with_status(1, BookingStatus::Confirmed)).unwrap();
+        assert!(confirmed.is_confirmed());
+
+        let result = TypedBooking::<Pending, _, _, _, _>::try_from(with_status(2, BookingStatus::Canceled));
+        assert!(matches!(result, Err(BookingError::InvalidStatus(_))));
+    }
+
+    #[test]
+    fn test_any_booking_round_trips_every_status() {
+        let statuses = [
+            BookingStatus::Pending,
+            BookingStatus::Confirmed,
+            BookingStatus::Canceled,
+            BookingStatus::Expired,
+            BookingStatus::Failed,
+            BookingStatus::Completed,
+            BookingStatus::Waitlisted,
+            BookingStatus::OnHold,
+            BookingStatus::CheckedIn,
+            BookingStatus::NoShow,
+            BookingStatus::Refunded,
+        ];
+
+        for status in statuses {
+            let booking = with_status(1, status.clone());
+            let any = AnyBooking::from(booking.clone());
+            assert_eq!(any.status(), status);
+            assert_eq!(Booking::from(any), booking);
+        }
+    }
+
+    #[test]
+    fn test_holds_and_waitlisted_bookings_become_pending() {
+        let hold = TypedBooking::<OnHold, _, _, _, _>::try_from(with_status(1, BookingStatus::OnHold)).unwrap();
+        let waitlisted = TypedBooking::<Waitlisted, _, _, _, _>::try_from(with_status(2, BookingStatus::Waitlisted)).unwrap();
+
+        assert!(hold.convert().is_pending());
+        assert!(waitlisted.promote().confirm().is_confirmed());
+    }
+
+    #[test]
+    fn test_serde_matches_dynamic_booking() {
+        let booking = pending(1).confirm();
+        let json = serde_json::to_string(&booking).unwrap();
+
+        assert_eq!(json, serde_json::to_string(booking.as_booking()).unwrap());
+        let restored: TypedBooking<Confirmed, MockBookable, u32, i64, ()> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, booking);
+        assert!(serde_json::from_str::<TypedBooking<Pending, MockBookable, u32, i64, ()>>(&json).is_err());
+        assert!(matches!(serde_json::from_str(&json).unwrap(), AnyBooking::<MockBookable, u32, i64, ()>::Confirmed(_)));
+    }
+
+    #[test]
+    fn test_typed_bookings_are_stored_as_bookings() {
+        let repository = InMemoryBookingRepository::new();
+        repository.insert(pending(1).into_booking()).unwrap();
+
+        let stored = repository.get(&1).unwrap().unwrap();
+        let AnyBooking::Pending(booking) = AnyBooking::from(stored) else {
+            panic!("expected a pending booking");
+        };
+        let confirmed = Booking::from(booking.confirm());
+        repository.update(confirmed).unwrap();
+
+        assert!(repository.get(&1).unwrap().unwrap().is_confirmed());
+    }
+
+    #[test]
+    fn test_transition_to_impls_match_can_transition_to() {
+        let pairs = typed_transitions!(
+            Pending, Confirmed, Canceled, Expired, Failed, Completed, Waitlisted, OnHold, CheckedIn, NoShow, Refunded
+        );
+
+        assert_eq!(pairs.len(), BookingStatus::ALL.len() * BookingStatus::ALL.len());
+        for status in BookingStatus::ALL {
+            assert!(pairs.iter().any(|(from, _, _)| from == status), "no marker for {:?}", status);
+        }
+        // No-op transitions to the same status are allowed dynamically but have no typed impl.
+        for (from, to, implemented) in pairs.into_iter().filter(|(from, to, _)| from != to) {
+            assert_eq!(
+                implemented,
+                from.can_transition_to(&to),
+                "TransitionTo<{:?}> for {:?} disagrees with can_transition_to",
+                to,
+                from
+            );
+        }
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod tests {
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::rules::{All, Any, BookingRule, MaxActivePerUser, MaxAdvanceWindow, MinLeadTime};
+    use booking_kit::traits::Bookable;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Ticket {
+        pub id: &'static str,
+        pub on_sale: bool,
+    }
+
+    impl Bookable for Ticket {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            self.on_sale
+        }
+
+        fn capacity(&self) -> Option<u32> {
+            Some(2)
+        }
+    }
+
+    type Rule = Box<dyn BookingRule<Ticket, u32, i64, ()>>;
+
+    const DAY: i64 = 86_400;
+
+    fn tickets(id: u32, on_sale: bool, quantity: u32, start: i64) -> Booking<Ticket, u32, i64, ()> {
+        let ticket = Ticket { id: "concert", on_sale };
+        BookingManager::create(id, Some(1), ticket, 0, None, None)
+            .with_quantity(quantity)
+            .with_period(start, start + DAY)
+    }
+
+    fn manager() -> BookingManager<InMemoryBookingRepository<Ticket, u32, i64, ()>, All<Rule>> {
+        let rules: All<Rule> = All(vec![Box::new(MinLeadTime(DAY)), Box::new(MaxActivePerUser(1))]);
+        BookingManager::new(InMemoryBookingRepository::new()).with_rules(rules)
+    }
+
+    #[test]
+    fn test_report_collects_every_failure() {
+        let manager = manager();
+        manager.try_insert(tickets(1, true, 1, 2 * DAY)).unwrap();
+
+        let report = manager.validate(&tickets(2, false, 3, 0));
+
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.codes(),
+            vec!["item_unavailable", "quantity_exceeded", "min_lead_time", "max_active_per_user"]
+        );
+        assert!(matches!(report.errors().next(), Some(BookingError::ItemUnavailable(_))));
+    }
+
+    #[test]
+    fn test_report_lists_inner_failures_of_any() {
+        let rules: Any<Rule> = Any(vec![Box::new(MinLeadTime(DAY)), Box::new(MaxAdvanceWindow(0))]);
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_rules(rules);
+
+        assert!(manager.validate(&tickets(1, true, 1, 2 * DAY)).is_valid());
+        let report = manager.validate(&tickets(2, true, 1, 3_600));
+
+        assert_eq!(report.codes(), vec!["min_lead_time", "max_advance_window"]);
+    }
+
+    #[test]
+    fn test_fail_fast_try_insert_still_reports_first_error() {
+        let manager = manager();
+
+        let result = manager.try_insert(tickets(1, false, 3, 0));
+
+        assert!(matches!(result, Err(BookingError::ItemUnavailable(_))));
+    }
+
+    #[test]
+    fn test_try_insert_reporting_stores_valid_booking() {
+        let manager = manager();
+
+        assert!(manager.try_insert_reporting(tickets(1, true, 2, 2 * DAY)).is_ok());
+        let report = manager.try_insert_reporting(tickets(2, true, 1, 2 * DAY)).unwrap_err();
+
+        assert!(report.contains("quantity_exceeded"));
+        assert!(report.contains("max_active_per_user"));
+        assert_eq!(manager.repository().len(), 1);
+    }
+
+    #[test]
+    fn test_report_serializes_codes_and_messages() {
+        let report = manager().validate(&tickets(1, false, 1, 2 * DAY));
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["issues"][0]["code"], "item_unavailable");
+        assert_eq!(json["issues"][0]["message"], "Booking item with ID concert is unavailable.");
+    }
+}
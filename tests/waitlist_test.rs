@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Barrier;
+    use std::thread;
+    use std::time::Duration;
+    use booking_kit::clock::FakeClock;
+    use booking_kit::error::BookingError;
+    use booking_kit::manager::BookingManager;
+    use booking_kit::model::booking::Booking;
+    use booking_kit::model::status::BookingStatus;
+    use booking_kit::repository::BookingRepository;
+    use booking_kit::repository::memory::InMemoryBookingRepository;
+    use booking_kit::sweeper::ExpirySweeper;
+    use booking_kit::traits::Bookable;
+    use booking_kit::waitlist::{BookingWaitlist, Waitlist, PROMOTION_REASON};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Room {
+        pub id: &'static str,
+    }
+
+    impl Bookable for Room {
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Flight {
+        pub seats: u32,
+    }
+
+    impl Bookable for Flight {
+        fn id(&self) -> &str {
+            "GA123"
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn capacity(&self) -> Option<u32> {
+            Some(self.seats)
+        }
+    }
+
+    /// A single-seat flight whose capacity lookup is slow, widening the window between checking
+    /// the seats left and storing a booking.
+    #[derive(Debug, Clone, PartialEq)]
+    struct LastSeat;
+
+    impl Bookable for LastSeat {
+        fn id(&self) -> &str {
+            "GA456"
+        }
+
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        fn capacity(&self) -> Option<u32> {
+            thread::sleep(Duration::from_millis(1));
+            Some(1)
+        }
+    }
+
+    fn night(booking_id: u32) -> Booking<Room, u32, i64, ()> {
+        BookingManager::create(booking_id, Some(booking_id), Room { id: "room-1" }, 0, Some(600), None).with_period(100, 200)
+    }
+
+    fn seats(booking_id: u32, quantity: u32) -> Booking<Flight, u32, i64, ()> {
+        BookingManager::create(booking_id, None, Flight { seats: 3 }, 0, None, None).with_quantity(quantity)
+    }
+
+    #[test]
+    fn test_cancel_promotes_first_waitlisted_booking() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_waitlist(Waitlist::fifo());
+        manager.try_insert(night(1)).unwrap();
+
+        let waiting = manager.try_create_or_waitlist(night(2), 0).unwrap();
+        manager.try_create_or_waitlist(night(3), 0).unwrap();
+        assert!(waiting.is_waitlisted());
+        assert_eq!(manager.waitlist().candidates("room-1").unwrap(), vec![2, 3]);
+
+        manager.cancel_booking(&1).unwrap();
+
+        let promoted = manager.repository().get(&2).unwrap().unwrap();
+        assert!(promoted.is_pending());
+        let change = promoted.last_change().unwrap();
+        assert_eq!(change.from, BookingStatus::Waitlisted);
+        assert_eq!(change.reason.as_deref(), Some(PROMOTION_REASON));
+        assert!(manager.repository().get(&3).unwrap().unwrap().is_waitlisted());
+        assert_eq!(manager.waitlist().candidates("room-1").unwrap(), vec![3]);
+    }
+
+    #[test]
+    fn test_priority_waitlist_promotes_highest_priority() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_waitlist(Waitlist::by_priority());
+        manager.try_insert(night(1)).unwrap();
+        manager.try_create_or_waitlist(night(2), 0).unwrap();
+        manager.try_create_or_waitlist(night(3), 5).unwrap();
+
+        manager.cancel_booking(&1).unwrap();
+
+        assert!(manager.repository().get(&3).unwrap().unwrap().is_pending());
+        assert!(manager.repository().get(&2).unwrap().unwrap().is_waitlisted());
+    }
+
+    #[test]
+    fn test_promotion_skips_entries_that_do_not_fit() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_waitlist(Waitlist::fifo());
+        manager.try_insert(seats(1, 2)).unwrap();
+        manager.try_insert(seats(2, 1)).unwrap();
+        assert!(manager.try_create_or_waitlist(seats(3, 2), 0).unwrap().is_waitlisted());
+        assert!(manager.try_create_or_waitlist(seats(4, 1), 0).unwrap().is_waitlisted());
+
+        manager.cancel_booking(&2).unwrap();
+
+        assert!(manager.repository().get(&3).unwrap().unwrap().is_waitlisted());
+        assert!(manager.repository().get(&4).unwrap().unwrap().is_pending());
+
+        manager.cancel_booking(&1).unwrap();
+        assert!(manager.repository().get(&3).unwrap().unwrap().is_pending());
+        assert!(manager.waitlist().is_empty());
+    }
+
+    #[test]
+    fn test_expiry_promotes_waitlisted_booking() {
+        let clock = FakeClock::new(0_i64);
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_waitlist(Waitlist::fifo());
+        manager.try_insert(night(1)).unwrap();
+        manager.try_create_or_waitlist(night(2), 0).unwrap();
+
+        clock.advance(600);
+        let report = ExpirySweeper::new(&clock).sweep_repository(&manager).unwrap();
+
+        assert_eq!(report.expired, vec![1]);
+        assert!(manager.repository().get(&2).unwrap().unwrap().is_pending());
+    }
+
+    #[test]
+    fn test_canceled_waitlisted_booking_leaves_waitlist() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new()).with_waitlist(Waitlist::fifo());
+        manager.try_insert(night(1)).unwrap();
+        manager.try_create_or_waitlist(night(2), 0).unwrap();
+
+        manager.cancel_booking(&2).unwrap();
+        manager.cancel_booking(&1).unwrap();
+
+        assert!(manager.waitlist().is_empty());
+        assert!(manager.repository().get(&2).unwrap().unwrap().is_canceled());
+    }
+
+    #[test]
+    fn test_without_waitlist_nothing_is_stored() {
+        let manager = BookingManager::new(InMemoryBookingRepository::new());
+        manager.try_insert(night(1)).unwrap();
+
+        let result = manager.try_create_or_waitlist(night(2), 0);
+
+        assert!(matches!(result, Err(BookingError::CreationFailed(_))));
+        assert_eq!(manager.repository().len(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_promotion_and_insert_cannot_overbook() {
+        for _ in 0..20 {
+            let manager = BookingManager::new(InMemoryBookingRepository::new()).with_waitlist(Waitlist::fifo());
+            let seat = |booking_id| BookingManager::create(booking_id, None, LastSeat, 0_i64, None, None::<()>);
+            manager.try_insert(seat(1)).unwrap();
+            assert!(manager.try_create_or_waitlist(seat(2), 0).unwrap().is_waitlisted());
+
+            // Frees the seat without promoting, so the promotion below races the insert.
+            let mut first = manager.repository().get(&1).unwrap().unwrap();
+            BookingManager::try_cancel(&mut first).unwrap();
+            manager.repository().update(first).unwrap();
+
+            let barrier = Barrier::new(2);
+            let (promoted, inserted) = thread::scope(|scope| {
+                let promotion = scope.spawn(|| {
+                    barrier.wait();
+                    manager.promote_waitlisted("GA456").unwrap()
+                });
+                let insert = scope.spawn(|| {
+                    barrier.wait();
+                    manager.try_insert(seat(3))
+                });
+                (promotion.join().unwrap(), insert.join().unwrap())
+            });
+
+            assert_eq!(promoted.len() + usize::from(inserted.is_ok()), 1);
+            let active = manager.repository().list_by_item("GA456").unwrap().iter().filter(|b| b.is_active()).count();
+            assert_eq!(active, 1);
+        }
+    }
+}